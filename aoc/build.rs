@@ -0,0 +1,13 @@
+// the compiler version is saved with benchmark results, see aoc_core::baseline
+fn main() {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let version = std::process::Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .unwrap_or_else(|| "unknown rustc".to_string());
+
+    println!("cargo::rustc-env=AOC_RUSTC_VERSION={version}");
+}
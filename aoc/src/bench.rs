@@ -0,0 +1,178 @@
+use crate::cli::Selection;
+use crate::days::Day;
+use crate::{label, select_days};
+use aoc_core::baseline::{self, Baseline, Sample, BASELINE_FILE};
+use aoc_core::config::{read_config, Part};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+/// Each case is solved until it has taken this long, within the limits on samples below
+const TIME_PER_CASE: Duration = Duration::from_secs(1);
+const MIN_SAMPLES: usize = 3;
+const MAX_SAMPLES: usize = 100;
+/// How many earlier results the trend shows
+const HISTORY: usize = 8;
+
+const RUSTC: &str = env!("AOC_RUSTC_VERSION");
+
+/// Times every selected case and appends the results to the baseline
+pub fn bench(selection: &Selection) -> ExitCode {
+    let days = match select_days(&selection.day) {
+        Ok(days) => days,
+        Err(code) => return code,
+    };
+    let mut baseline = match Baseline::load() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut samples = Vec::new();
+    let failures = each_case(&days, selection, |day, part| {
+        let sample = measure(day, part)?;
+        println!(
+            "{} :: {:.2?} (parse {:.2?}, solve {:.2?})",
+            label(day, part),
+            sample.total(),
+            sample.parse,
+            sample.solve
+        );
+        samples.push(sample);
+        Ok(true)
+    });
+
+    if !samples.is_empty() {
+        match baseline.save(&baseline::machine(), RUSTC, &samples) {
+            Ok(run) => println!("saved as run {run} in {BASELINE_FILE}"),
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    match failures {
+        0 => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+/// Times every selected case against its last result on this machine,
+/// failing if any got slower by more than the threshold
+pub fn compare(selection: &Selection, threshold: f64) -> ExitCode {
+    let days = match select_days(&selection.day) {
+        Ok(days) => days,
+        Err(code) => return code,
+    };
+    let baseline = match Baseline::load() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let machine = baseline::machine();
+
+    let failures = each_case(&days, selection, |day, part| {
+        let sample = measure(day, part)?;
+        let label = label(day, part);
+        let history = baseline.history(&machine, day.name, part.part, &part.name);
+
+        let Some(last) = history.last() else {
+            println!(
+                "{label} :: {:.2?} no earlier result on this machine",
+                sample.total()
+            );
+            return Ok(true);
+        };
+
+        let change = baseline::slowdown(last.total(), sample.total());
+        let timings = history
+            .iter()
+            .rev()
+            .take(HISTORY)
+            .rev()
+            .map(|s| s.total())
+            .chain([sample.total()])
+            .collect::<Vec<_>>();
+        let slower = change > threshold;
+
+        println!(
+            "{label} :: {:.2?} {change:+.1}% on {:.2?} {}{}",
+            sample.total(),
+            last.total(),
+            baseline::trend(&timings),
+            if slower { " SLOWER" } else { "" }
+        );
+        Ok(!slower)
+    });
+
+    match failures {
+        0 => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+/// Calls `f` on each selected case, returning how many failed or were flagged
+fn each_case(
+    days: &[&Day],
+    selection: &Selection,
+    mut f: impl FnMut(&Day, &Part) -> Result<bool, String>,
+) -> usize {
+    let mut failures = 0;
+    for &day in days {
+        let parts = match read_config(day.name) {
+            Ok(parts) => parts,
+            Err(e) => {
+                println!("{:<8} :: {e}", day.name);
+                failures += 1;
+                continue;
+            }
+        };
+
+        for part in parts.iter().filter(|p| selection.includes(p)) {
+            match f(day, part) {
+                Ok(true) => {}
+                Ok(false) => failures += 1,
+                Err(e) => {
+                    println!("{} <{}> failed :: {e}", label(day, part), part.input);
+                    failures += 1;
+                }
+            }
+        }
+    }
+    failures
+}
+
+/// The median parse and solve times over repeated runs, once the answer is known to be right
+fn measure(day: &Day, part: &Part) -> Result<Sample, String> {
+    let mut parses = Vec::new();
+    let mut solves = Vec::new();
+    let now = Instant::now();
+
+    while parses.len() < MAX_SAMPLES
+        && (parses.len() < MIN_SAMPLES || now.elapsed() < TIME_PER_CASE)
+    {
+        let (answer, timings) = (day.run)(&part.input, part.part).map_err(|e| e.to_string())?;
+        if answer != part.expected {
+            return Err(format!("gave {answer} but expected {}", part.expected));
+        }
+        parses.push(timings.parse);
+        solves.push(timings.solve);
+    }
+
+    Ok(Sample {
+        day: day.name.to_string(),
+        part: part.part,
+        case: part.name.clone(),
+        parse: median(parses),
+        solve: median(solves),
+    })
+}
+
+fn median(mut timings: Vec<Duration>) -> Duration {
+    timings.sort();
+    timings[timings.len() / 2]
+}
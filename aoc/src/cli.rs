@@ -0,0 +1,413 @@
+use aoc_core::config::{Mode, Part};
+use aoc_core::record::Pace;
+use aoc_core::visual::Visual;
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+usage: aoc run <dayN|all> [--part <1|2>] [--case <name|test|real>] [--jobs <n>] [--slow <ms>] [--tsv]
+                              [--visual <none|plain|term[:ms]|dump:dir|gif:dir|cast:dir>]
+                              [--fps <n>] [--every <n>]
+       aoc watch <dayN>
+       aoc bench <dayN|all> [--part <1|2>] [--case <name|test|real>]
+       aoc compare <dayN|all> [--part <1|2>] [--case <name|test|real>] [--threshold <percent>]
+       aoc check-config [dayN|all]
+       aoc new <dayN>
+       aoc download <dayN>
+       aoc submit <dayN> <1|2> [answer]
+       aoc encrypt <file>...
+       aoc decrypt <file>...
+       aoc keygen
+run solves the cases on --jobs threads, one per core by default, and marks those over --slow,
+--tsv writing the rows tab separated without the table around them, and --visual showing the
+solvers at work as plain text, a terminal animation holding each frame for ms, frame files,
+or a GIF or asciinema recording per case playing --fps frames a second, keeping one in --every
+bench and compare time the real cases unless --case says otherwise";
+
+/// How much slower than its last saved time a case may get before compare flags it
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// How long a case may take in a run before it is marked as slow
+pub const DEFAULT_SLOW: Duration = Duration::from_secs(1);
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(Selection, RunOptions),
+    /// rebuild and rerun the day whenever its source, config or inputs change
+    Watch(String),
+    /// time the cases and save the results to the baseline
+    Bench(Selection),
+    /// time the cases and flag any slower than the baseline by more than the threshold percentage
+    Compare(Selection, f64),
+    /// validate the config of one day, or all of them when `None`
+    CheckConfig(Option<String>),
+    /// create a day crate from the day16 layout, see scaffold
+    New(String),
+    /// fetch the day's puzzle input into the input directory
+    Download(String),
+    /// send an answer, or the real case's answer when none is given, recording it if right
+    Submit(String, u8, Option<String>),
+    /// write an encrypted copy of each file beside it, see aoc_core::crypt
+    Encrypt(Vec<String>),
+    /// write the plain file back out from each encrypted one
+    Decrypt(Vec<String>),
+    /// print a new random key
+    Keygen,
+}
+
+/// How run solves the cases and shows them
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    /// threads to solve on, one per core when `None`
+    pub jobs: Option<usize>,
+    /// cases taking longer are marked as slow
+    pub slow: Duration,
+    /// rows of tab separated values rather than a table, for another program to read
+    pub tsv: bool,
+    /// where the solvers' frames go, cases taking turns when it is the terminal
+    pub visual: Visual,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            jobs: None,
+            slow: DEFAULT_SLOW,
+            tsv: false,
+            visual: Visual::None,
+        }
+    }
+}
+
+/// Which config lines to run, `None` meaning all of them
+#[derive(Debug, PartialEq)]
+pub struct Selection {
+    pub day: Option<String>,
+    pub part: Option<u8>,
+    /// a case name, or a mode to pick every case in it
+    pub case: Option<String>,
+}
+
+impl Selection {
+    pub fn includes(&self, part: &Part) -> bool {
+        self.part.is_none_or(|p| p == part.part)
+            && self
+                .case
+                .as_ref()
+                .is_none_or(|case| *case == part.name || case.parse::<Mode>() == Ok(part.mode))
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter().map(String::as_str);
+
+    let command = match args.next() {
+        Some(command @ ("run" | "bench" | "compare")) => command,
+        Some("check-config") => {
+            let day = match args.next() {
+                None => None,
+                day => day_arg(day)?,
+            };
+            return match args.next() {
+                Some(extra) => Err(format!("unexpected argument {extra}")),
+                None => Ok(Command::CheckConfig(day)),
+            };
+        }
+        Some(command @ ("encrypt" | "decrypt")) => {
+            let files = args.map(str::to_string).collect::<Vec<_>>();
+            return match (command, files.is_empty()) {
+                (_, true) => Err(format!("nothing to {command}")),
+                ("encrypt", _) => Ok(Command::Encrypt(files)),
+                _ => Ok(Command::Decrypt(files)),
+            };
+        }
+        Some(command @ ("new" | "download" | "watch")) => {
+            let day = args.next().ok_or("expected a day")?.to_string();
+            return match (args.next(), command) {
+                (Some(extra), _) => Err(format!("unexpected argument {extra}")),
+                (None, "new") => Ok(Command::New(day)),
+                (None, "watch") => Ok(Command::Watch(day)),
+                (None, _) => Ok(Command::Download(day)),
+            };
+        }
+        Some("submit") => {
+            let day = args.next().ok_or("expected a day")?.to_string();
+            let part = match args.next().map(|p| (p, p.parse::<u8>())) {
+                Some((_, Ok(part @ 1..=2))) => part,
+                Some((part, _)) => return Err(format!("unknown part {part}")),
+                None => return Err("expected a part".to_string()),
+            };
+            let answer = args.next().map(str::to_string);
+            return match args.next() {
+                Some(extra) => Err(format!("unexpected argument {extra}")),
+                None => Ok(Command::Submit(day, part, answer)),
+            };
+        }
+        Some("keygen") => {
+            return match args.next() {
+                Some(extra) => Err(format!("unexpected argument {extra}")),
+                None => Ok(Command::Keygen),
+            };
+        }
+        Some(other) => return Err(format!("unknown command '{other}'")),
+        None => return Err("missing command".to_string()),
+    };
+
+    let day = day_arg(args.next())?;
+
+    let mut selection = Selection {
+        day,
+        part: None,
+        case: None,
+    };
+    let mut threshold = DEFAULT_THRESHOLD;
+    let mut options = RunOptions::default();
+    let mut pace: Option<Pace> = None;
+
+    while let Some(flag) = args.next() {
+        if flag == "--tsv" && command == "run" {
+            options.tsv = true;
+            continue;
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {flag}"))?;
+        match flag {
+            "--part" => match value.parse::<u8>() {
+                Ok(part @ 1..=2) => selection.part = Some(part),
+                _ => return Err(format!("unknown part {value}")),
+            },
+            "--case" => selection.case = Some(value.to_string()),
+            "--threshold" if command == "compare" => match value.parse::<f64>() {
+                Ok(percent) if percent >= 0.0 => threshold = percent,
+                _ => return Err(format!("the threshold should be a percentage, not {value}")),
+            },
+            "--jobs" if command == "run" => match value.parse::<usize>() {
+                Ok(count) if count > 0 => options.jobs = Some(count),
+                _ => return Err(format!("--jobs should be a count of threads, not {value}")),
+            },
+            "--slow" if command == "run" => match value.parse::<u64>() {
+                Ok(ms) => options.slow = Duration::from_millis(ms),
+                _ => return Err(format!("--slow should be milliseconds, not {value}")),
+            },
+            "--visual" if command == "run" => options.visual = value.parse()?,
+            "--fps" if command == "run" => match value.parse::<u32>() {
+                Ok(fps) if fps > 0 => pace.get_or_insert_with(Pace::default).fps = fps,
+                _ => return Err(format!("--fps should be frames a second, not {value}")),
+            },
+            "--every" if command == "run" => match value.parse::<usize>() {
+                Ok(every) if every > 0 => pace.get_or_insert_with(Pace::default).every = every,
+                _ => return Err(format!("--every should be a count of frames, not {value}")),
+            },
+            _ => return Err(format!("unknown option {flag}")),
+        }
+    }
+
+    if let Some(pace) = pace {
+        options.visual = options.visual.paced(pace)?;
+    }
+
+    // timings of the small examples are mostly noise
+    if command != "run" && selection.case.is_none() {
+        selection.case = Some("real".to_string());
+    }
+
+    Ok(match command {
+        "bench" => Command::Bench(selection),
+        "compare" => Command::Compare(selection, threshold),
+        _ => Command::Run(selection, options),
+    })
+}
+
+fn day_arg(arg: Option<&str>) -> Result<Option<String>, String> {
+    match arg {
+        Some("all") => Ok(None),
+        Some(day) if !day.starts_with("--") => Ok(Some(day.to_string())),
+        _ => Err("expected a day or 'all'".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_core::answer::Answer;
+    use aoc_core::files::Input;
+
+    fn parse(args: &str) -> Result<Command, String> {
+        let args = args.split(' ').map(str::to_string).collect::<Vec<_>>();
+        parse_args(&args)
+    }
+
+    #[test]
+    fn run_one_case() {
+        assert_eq!(
+            Ok(Command::Run(
+                Selection {
+                    day: Some("day16".to_string()),
+                    part: Some(2),
+                    case: Some("real".to_string()),
+                },
+                RunOptions::default()
+            )),
+            parse("run day16 --part 2 --case real")
+        );
+    }
+
+    #[test]
+    fn run_all() {
+        assert_eq!(
+            Ok(Command::Run(
+                Selection {
+                    day: None,
+                    part: None,
+                    case: None,
+                },
+                RunOptions::default()
+            )),
+            parse("run all")
+        );
+        assert!(matches!(
+            parse("run all --jobs 4 --tsv --slow 250"),
+            Ok(Command::Run(_, RunOptions { jobs: Some(4), slow, tsv: true, .. }))
+                if slow == Duration::from_millis(250)
+        ));
+        assert!(matches!(
+            parse("run day6 --visual term:20"),
+            Ok(Command::Run(_, RunOptions { visual: Visual::Terminal(delay), .. }))
+                if delay == Duration::from_millis(20)
+        ));
+        assert!(parse("run day6 --visual dump").is_err());
+        assert_eq!(
+            Ok(Command::Run(
+                Selection {
+                    day: Some("day6".to_string()),
+                    part: None,
+                    case: None,
+                },
+                RunOptions {
+                    visual: Visual::Gif("shared".into(), Pace { fps: 25, every: 10 }),
+                    ..RunOptions::default()
+                }
+            )),
+            parse("run day6 --every 10 --visual gif:shared --fps 25")
+        );
+        assert!(parse("run day6 --visual term --fps 25").is_err());
+        assert!(parse("run day6 --visual cast:shared --every 0").is_err());
+        assert!(parse("bench day6 --visual plain").is_err());
+        assert!(parse("run all --jobs 0").is_err());
+        assert_eq!(
+            Ok(Command::Watch("day16".to_string())),
+            parse("watch day16")
+        );
+        assert!(parse("watch day16 day5").is_err());
+        assert!(parse("bench all --jobs 2").is_err());
+    }
+
+    #[test]
+    fn bench_and_compare_default_to_real_cases() {
+        let real = Selection {
+            day: Some("day7".to_string()),
+            part: None,
+            case: Some("real".to_string()),
+        };
+        assert_eq!(Ok(Command::Bench(real)), parse("bench day7"));
+        assert_eq!(
+            Ok(Command::Compare(
+                Selection {
+                    day: None,
+                    part: Some(1),
+                    case: Some("example".to_string()),
+                },
+                5.0
+            )),
+            parse("compare all --part 1 --case example --threshold 5")
+        );
+        assert!(matches!(
+            parse("compare all"),
+            Ok(Command::Compare(_, DEFAULT_THRESHOLD))
+        ));
+        assert!(parse("compare all --threshold fast").is_err());
+        assert!(parse("bench all --threshold 5").is_err());
+    }
+
+    #[test]
+    fn check_config() {
+        assert_eq!(Ok(Command::CheckConfig(None)), parse("check-config"));
+        assert_eq!(
+            Ok(Command::CheckConfig(Some("day5".to_string()))),
+            parse("check-config day5")
+        );
+        assert!(parse("check-config day5 day6").is_err());
+    }
+
+    #[test]
+    fn download_and_submit() {
+        assert_eq!(
+            Ok(Command::Download("day5".to_string())),
+            parse("download day5")
+        );
+        assert!(parse("download").is_err());
+        assert_eq!(
+            Ok(Command::Submit("day5".to_string(), 2, None)),
+            parse("submit day5 2")
+        );
+        assert_eq!(
+            Ok(Command::Submit(
+                "day5".to_string(),
+                1,
+                Some("6,1".to_string())
+            )),
+            parse("submit day5 1 6,1")
+        );
+        assert!(parse("submit day5 3").is_err());
+        assert!(parse("submit day5").is_err());
+    }
+
+    #[test]
+    fn encrypt_and_decrypt_files() {
+        assert_eq!(
+            Ok(Command::Encrypt(vec![
+                "day5.txt".to_string(),
+                "day5.toml".to_string()
+            ])),
+            parse("encrypt day5.txt day5.toml")
+        );
+        assert_eq!(
+            Ok(Command::Decrypt(vec!["day5.txt.enc".to_string()])),
+            parse("decrypt day5.txt.enc")
+        );
+        assert!(parse("encrypt").is_err());
+        assert_eq!(Ok(Command::Keygen), parse("keygen"));
+        assert!(parse("keygen day5").is_err());
+    }
+
+    #[test]
+    fn case_matches_name_or_mode() {
+        let part = Part {
+            name: "example".to_string(),
+            part: 1,
+            mode: Mode::Test,
+            input: Input::File("day1-test.txt".to_string()),
+            expected: Answer::Signed(17),
+            tags: Vec::new(),
+            notes: None,
+        };
+        let selection = |case: &str| Selection {
+            day: None,
+            part: None,
+            case: Some(case.to_string()),
+        };
+
+        assert!(selection("example").includes(&part));
+        assert!(selection("test").includes(&part));
+        assert!(!selection("real").includes(&part));
+        assert!(!selection("other").includes(&part));
+    }
+
+    #[test]
+    fn rejects_bad_options() {
+        assert!(parse("run day16 --part 3").is_err());
+        assert!(parse("run day16 --part").is_err());
+        assert!(parse("run day16 --verbose 1").is_err());
+        assert!(parse("walk day16").is_err());
+    }
+}
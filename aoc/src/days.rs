@@ -0,0 +1,25 @@
+use aoc_core::answer::Answer;
+use aoc_core::files::Input;
+use aoc_core::misc::AResult;
+use aoc_core::runner::{self, Timings};
+
+/// A day with its Solution erased so every day fits in one table
+pub struct Day {
+    pub name: &'static str,
+    pub run: fn(&Input, u8) -> AResult<(Answer, Timings)>,
+}
+
+macro_rules! days {
+    ($($day:ident),* $(,)?) => {
+        pub const DAYS: &[Day] = &[$(Day {
+            name: stringify!($day),
+            run: runner::run_timed::<$day::Solver>,
+        }),*];
+    };
+}
+
+days!(day3, day4, day5, day6, day7, day7_v2, day7_v3, day8, day11, day13, day16);
+
+pub fn find(name: &str) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.name == name)
+}
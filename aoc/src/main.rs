@@ -0,0 +1,173 @@
+mod bench;
+mod cli;
+mod days;
+mod remote;
+mod scaffold;
+mod summary;
+mod watch;
+
+use aoc_core::config::{check_config, Part};
+use aoc_core::crypt::{self, Key};
+use aoc_core::files;
+use cli::Command;
+use days::Day;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    match cli::parse_args(&args) {
+        Ok(Command::Run(selection, options)) => summary::run(&selection, &options),
+        Ok(Command::Watch(day)) => watch::watch(&day),
+        Ok(Command::Bench(selection)) => bench::bench(&selection),
+        Ok(Command::Compare(selection, threshold)) => bench::compare(&selection, threshold),
+        Ok(Command::CheckConfig(day)) => check(&day),
+        Ok(Command::New(day)) => new_day(&day),
+        Ok(Command::Download(day)) => remote::download(&day),
+        Ok(Command::Submit(day, part, answer)) => remote::submit(&day, part, &answer),
+        Ok(Command::Encrypt(files)) => seal(&files, true),
+        Ok(Command::Decrypt(files)) => seal(&files, false),
+        Ok(Command::Keygen) => {
+            println!("{}", Key::generate());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("{}", cli::USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn select_days(day: &Option<String>) -> Result<Vec<&'static Day>, ExitCode> {
+    match day {
+        Some(name) => match days::find(name) {
+            Some(day) => Ok(vec![day]),
+            None => {
+                eprintln!("unknown day {name}");
+                Err(ExitCode::from(2))
+            }
+        },
+        None => Ok(days::DAYS.iter().collect()),
+    }
+}
+
+/// Prints every problem in each config, or a single ok line when there are none
+fn check(day: &Option<String>) -> ExitCode {
+    let days = match select_days(day) {
+        Ok(days) => days,
+        Err(code) => return code,
+    };
+
+    let mut failures = 0;
+    for day in days {
+        match check_config(day.name) {
+            Ok(diagnostics) if diagnostics.is_empty() => println!("{:<8} :: ok", day.name),
+            Ok(diagnostics) => {
+                failures += 1;
+                for diagnostic in diagnostics {
+                    println!("{:<8} :: {diagnostic}", day.name);
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                println!("{:<8} :: {e}", day.name);
+            }
+        }
+    }
+
+    if failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Scaffolds the day in this workspace, its config going beside the other inputs
+fn new_day(day: &str) -> ExitCode {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let search_paths = files::search_paths();
+    let input_dir = search_paths
+        .iter()
+        .find(|dir| dir.is_dir())
+        .unwrap_or(&search_paths[0]);
+
+    match scaffold::new_day(root, input_dir, day) {
+        Ok(done) => {
+            for line in done {
+                println!("{day} :: {line}");
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{day} :: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Encrypts each file to <file>.enc, replacing any earlier copy, or decrypts <file>.enc
+/// back to <file>, never replacing a plain file that is already there.
+/// A file not found as given is looked for on the input search paths.
+fn seal(files: &[String], encrypting: bool) -> ExitCode {
+    let key = match Key::from_env() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failures = 0;
+    for file in files {
+        match seal_file(&key, file, encrypting) {
+            Ok(written) => println!("{file} :: wrote {}", written.display()),
+            Err(e) => {
+                failures += 1;
+                println!("{file} :: {e}");
+            }
+        }
+    }
+
+    if failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn seal_file(key: &Key, file: &str, encrypting: bool) -> Result<PathBuf, String> {
+    let path = match Path::new(file).exists() {
+        true => PathBuf::from(file),
+        false => files::find_input(file).map_err(|e| e.to_string())?,
+    };
+    let data = std::fs::read(&path).map_err(|e| e.to_string())?;
+    let name = path.to_string_lossy();
+
+    let (target, output) = match (encrypting, crypt::is_encrypted(&data)) {
+        (true, false) => (
+            PathBuf::from(format!("{name}{}", crypt::EXTENSION)),
+            crypt::encrypt(key, &data),
+        ),
+        (false, true) => match name.strip_suffix(crypt::EXTENSION) {
+            Some(plain) if Path::new(plain).exists() => {
+                return Err(format!("{plain} already exists, remove it first"))
+            }
+            Some(plain) => (PathBuf::from(plain), crypt::decrypt(key, &data)?),
+            None => return Err(format!("expected a name ending {}", crypt::EXTENSION)),
+        },
+        (true, true) => return Err("already encrypted".to_string()),
+        (false, false) => return Err("not encrypted".to_string()),
+    };
+
+    std::fs::write(&target, output).map_err(|e| e.to_string())?;
+    Ok(target)
+}
+
+fn label(day: &Day, part: &Part) -> String {
+    format!(
+        "{:<8} part {} {:<4} {:<10}",
+        day.name, part.part, part.mode, part.name
+    )
+}
@@ -0,0 +1,118 @@
+use crate::days;
+use aoc_core::answer::Answer;
+use aoc_core::client::{Client, Verdict};
+use aoc_core::config::{read_config, record_answer, Mode};
+use aoc_core::error::Error;
+use aoc_core::files::{input_exists, input_path, write_input};
+use aoc_core::history::History;
+use aoc_core::misc::{puzzle_day, puzzle_input_name, AResult};
+use std::process::ExitCode;
+
+fn day_number(day: &str) -> Result<u32, ExitCode> {
+    puzzle_day(day).ok_or_else(|| {
+        eprintln!("expected a day such as day5, not {day}");
+        ExitCode::from(2)
+    })
+}
+
+/// Saves the day's input unless it is already there, the server asks not to be fetched from needlessly
+pub fn download(day: &str) -> ExitCode {
+    let number = match day_number(day) {
+        Ok(number) => number,
+        Err(code) => return code,
+    };
+    let name = puzzle_input_name(number);
+
+    if input_exists(&name) {
+        println!("{day} :: already have {}", input_path(&name).display());
+        return ExitCode::SUCCESS;
+    }
+
+    let written = Client::from_env()
+        .and_then(|client| client.download(number))
+        .and_then(|input| write_input(&name, &input));
+    match written {
+        Ok(path) => {
+            println!("{day} :: wrote {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{day} :: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Sends the answer, or the one the day's real case gives, and records it in the config when right
+pub fn submit(day: &str, part: u8, answer: &Option<String>) -> ExitCode {
+    let number = match day_number(day) {
+        Ok(number) => number,
+        Err(code) => return code,
+    };
+
+    let label = format!("{day} part {part}");
+    let answer = match answer {
+        Some(answer) => Ok(answer.parse::<Answer>().unwrap()),
+        None => solve_real_case(day, part),
+    };
+    let answer = match answer {
+        Ok(answer) => answer,
+        Err(e) => {
+            println!("{label} :: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut history = match History::load() {
+        Ok(history) => history,
+        Err(e) => {
+            println!("{label} :: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // a known wrong answer would only cost another wait before the next try
+    if let Some(warning) = history.check(number, part, &answer) {
+        println!("{label} :: not submitting {answer}, {warning}");
+        return ExitCode::FAILURE;
+    }
+
+    println!("{label} :: submitting {answer}");
+    let verdict = match Client::from_env().and_then(|client| client.submit(number, part, &answer)) {
+        Ok(verdict) => verdict,
+        Err(e) => {
+            println!("{label} :: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("{label} :: {answer} is {verdict}");
+
+    if let Err(e) = history.save(number, part, &answer, &verdict) {
+        println!("{label} :: not added to the answer history :: {e}");
+    }
+
+    if verdict != Verdict::Right {
+        return ExitCode::FAILURE;
+    }
+    match record_answer(day, part, &answer) {
+        Ok(path) => println!("{label} :: recorded in {}", path.display()),
+        Err(e) => println!("{label} :: could not be recorded :: {e}"),
+    }
+    ExitCode::SUCCESS
+}
+
+fn solve_real_case(day: &str, part: u8) -> AResult<Answer> {
+    let solver = days::find(day)
+        .ok_or_else(|| Error::solver(format!("no solver for {day}, give the answer instead")))?;
+    let case = read_config(day)?
+        .into_iter()
+        .find(|case| case.part == part && case.mode == Mode::Real)
+        .ok_or_else(|| {
+            Error::config(
+                &format!("{day} config"),
+                None,
+                format!("no real case for part {part}, give the answer instead"),
+            )
+        })?;
+
+    (solver.run)(&case.input, part).map(|(answer, _)| answer)
+}
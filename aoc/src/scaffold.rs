@@ -0,0 +1,267 @@
+//! `aoc new dayN`: a new day crate laid out like day16, with its cases run as tests
+//! and benchmarks, and a Solver ready to be filled in.
+
+use std::path::Path;
+
+/// The day whose layout new days copy
+const TEMPLATE_DAY: &str = "day16";
+
+/// Copied as they are, every day shares them
+const COPIED: &[&str] = &["build.rs", "src/main.rs"];
+
+/// Copied with the template day's name replaced
+const RENAMED: &[&str] = &["tests/cases.rs", "benches/speed.rs"];
+
+const LIB: &str = "mod day;
+
+pub use day::Solver;
+";
+
+const DAY: &str = "use aoc_core::error::Error;
+use aoc_core::misc::AResult;
+use aoc_core::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<String>;
+    type Answer = i64;
+
+    fn parse(input: &str) -> AResult<Vec<String>> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part1(_lines: &Vec<String>) -> AResult<i64> {
+        Err(Error::solver(\"part 1 is not solved yet\"))
+    }
+
+    fn part2(_lines: &Vec<String>) -> AResult<i64> {
+        Err(Error::solver(\"part 2 is not solved yet\"))
+    }
+}
+";
+
+fn config(day: &str, input: &str) -> String {
+    format!(
+        "# {day}, one [[case]] per input, see aoc_core::config
+#
+# [[case]]
+# name = \"example\"
+# part = 1
+# mode = \"test\"
+# input_text = \"\"\"
+# the example from the puzzle
+# \"\"\"
+# expected = 0
+#
+# [[case]]
+# name = \"real\"
+# part = 1
+# mode = \"real\"
+# input = \"{input}\"
+# expected = 0
+"
+    )
+}
+
+/// Creates the crate under `root` and adds it to the workspace and the aoc runner,
+/// writing a config skeleton to `input_dir` unless the day already has one.
+/// Returns a line for each thing done.
+pub fn new_day(root: &Path, input_dir: &Path, day: &str) -> Result<Vec<String>, String> {
+    let number = aoc_core::misc::puzzle_day(day)
+        .filter(|_| day.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+        .ok_or_else(|| format!("expected a name such as day17 or day17_v2, not {day}"))?;
+
+    let template = root.join(TEMPLATE_DAY);
+    let target = root.join(day);
+    if target.exists() {
+        return Err(format!("{} already exists", target.display()));
+    }
+
+    let mut done = Vec::new();
+    let write = |relative: &str, text: &str| -> Result<(), String> {
+        let path = target.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| std::fs::write(&path, text))
+            .map_err(|e| format!("<{}> :: {e}", path.display()))
+    };
+    let read = |relative: &str| {
+        let path = template.join(relative);
+        std::fs::read_to_string(&path).map_err(|e| format!("<{}> :: {e}", path.display()))
+    };
+
+    write("Cargo.toml", &manifest(&read("Cargo.toml")?, day))?;
+    for file in COPIED {
+        write(file, &read(file)?)?;
+    }
+    for file in RENAMED {
+        write(file, &read(file)?.replace(TEMPLATE_DAY, day))?;
+    }
+    write("src/lib.rs", LIB)?;
+    write("src/day.rs", DAY)?;
+    done.push(format!("created {}", target.display()));
+
+    edit(&root.join("Cargo.toml"), |text| add_member(text, day))?;
+    edit(&root.join("aoc/Cargo.toml"), |text| {
+        add_dependency(text, day)
+    })?;
+    edit(&root.join("aoc/src/days.rs"), |text| add_day(text, day))?;
+    done.push(format!("added {day} to the workspace and the aoc runner"));
+
+    let input = aoc_core::misc::puzzle_input_name(number);
+    let config_path = input_dir.join(format!("{day}.toml"));
+    let legacy_path = input_dir.join(format!("{day}.config"));
+    if config_path.exists() || legacy_path.exists() {
+        done.push(format!(
+            "kept the config already in {}",
+            input_dir.display()
+        ));
+    } else {
+        std::fs::create_dir_all(input_dir)
+            .and_then(|_| std::fs::write(&config_path, config(day, &input)))
+            .map_err(|e| format!("<{}> :: {e}", config_path.display()))?;
+        done.push(format!("wrote {}", config_path.display()));
+    }
+
+    Ok(done)
+}
+
+fn edit(path: &Path, change: impl Fn(&str) -> Result<String, String>) -> Result<(), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("<{}> :: {e}", path.display()))?;
+    let changed = change(&text).map_err(|e| format!("<{}> :: {e}", path.display()))?;
+    std::fs::write(path, changed).map_err(|e| format!("<{}> :: {e}", path.display()))
+}
+
+/// The template's manifest renamed, keeping only the dependencies every day has
+fn manifest(template: &str, day: &str) -> String {
+    let mut section = "";
+    let mut lines = Vec::new();
+
+    for line in template.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            section = trimmed;
+        } else if section == "[dependencies]"
+            && !trimmed.is_empty()
+            && !trimmed.starts_with("aoc_core")
+        {
+            continue;
+        }
+        lines.push(line.replace(&format!("\"{TEMPLATE_DAY}\""), &format!("\"{day}\"")));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Adds the day to the end of the workspace members list
+fn add_member(text: &str, day: &str) -> Result<String, String> {
+    // not default-members
+    let start = text.find("\nmembers = [").ok_or("no members list")? + 1;
+    let end = start
+        + text[start..]
+            .find(']')
+            .ok_or("no end to the members list")?;
+    let members = &text[start..end];
+
+    if members.contains(&format!("\"{day}\"")) {
+        return Ok(text.to_string());
+    }
+    let indent = members
+        .lines()
+        .nth(1)
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .unwrap_or("\t");
+    let members = format!("{}\n{indent}\"{day}\",\n", members.trim_end());
+
+    Ok(format!("{}{members}{}", &text[..start], &text[end..]))
+}
+
+/// Adds a path dependency on the day after the last one
+fn add_dependency(text: &str, day: &str) -> Result<String, String> {
+    let mut lines = text.lines().map(str::to_string).collect::<Vec<_>>();
+    let last = lines
+        .iter()
+        .rposition(|line| line.contains("path = \"../day"))
+        .ok_or("no day dependencies")?;
+    lines.insert(last + 1, format!("{day} = {{ path = \"../{day}\" }}"));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the day to the end of the days! list
+fn add_day(text: &str, day: &str) -> Result<String, String> {
+    let start = text.find("days!(").ok_or("no days! list")?;
+    let end = start + text[start..].find(')').ok_or("no end to the days! list")?;
+    let list = text[start..end].trim_end().trim_end_matches(',');
+    Ok(format!("{}{list}, {day}{}", &text[..start], &text[end..]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn edits_the_workspace() {
+        let workspace =
+            "[workspace]\ndefault-members = [\"aoc\"]\nmembers = [\n\t\"aoc\",\n\t\"day16\",\n]\n\n[workspace.dependencies]\n";
+        assert_eq!(
+            "[workspace]\ndefault-members = [\"aoc\"]\nmembers = [\n\t\"aoc\",\n\t\"day16\",\n\t\"day17\",\n]\n\n[workspace.dependencies]\n",
+            add_member(workspace, "day17").unwrap()
+        );
+        assert_eq!(workspace, add_member(workspace, "day16").unwrap());
+
+        assert_eq!(
+            "[dependencies]\nday16 = { path = \"../day16\" }\nday17 = { path = \"../day17\" }\n\n[[bin]]\n",
+            add_dependency("[dependencies]\nday16 = { path = \"../day16\" }\n\n[[bin]]\n", "day17").unwrap()
+        );
+        assert_eq!(
+            "days!(day5, day16, day17);\n",
+            add_day("days!(day5, day16);\n", "day17").unwrap()
+        );
+    }
+
+    #[test]
+    fn keeps_only_shared_dependencies() {
+        let template = "[package]\nname = \"day16\"\n\n[dependencies]\naoc_core.workspace = true\npriority-queue = \"2.1\"\n\n[lib]\nname = \"day16\"\n";
+        assert_eq!(
+            "[package]\nname = \"day17\"\n\n[dependencies]\naoc_core.workspace = true\n\n[lib]\nname = \"day17\"\n",
+            manifest(template, "day17")
+        );
+    }
+
+    #[test]
+    fn creates_a_day_from_the_template() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+
+        let copy = |relative: &str| {
+            let path = root.join(relative);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::copy(workspace.join(relative), path).unwrap();
+        };
+        copy("Cargo.toml");
+        copy("aoc/Cargo.toml");
+        copy("aoc/src/days.rs");
+        copy("day16/Cargo.toml");
+        for file in COPIED.iter().chain(RENAMED) {
+            copy(&format!("day16/{file}"));
+        }
+
+        let input = root.join("input");
+        new_day(&root, &input, "day17").unwrap();
+
+        let read = |relative: &str| std::fs::read_to_string(root.join(relative)).unwrap();
+        assert!(read("Cargo.toml").contains("\t\"day17\",\n]"));
+        assert!(read("aoc/Cargo.toml").contains("day17 = { path = \"../day17\" }"));
+        assert!(read("aoc/src/days.rs").contains(", day17);"));
+        assert!(read("day17/tests/cases.rs").contains("day17::Solver"));
+        assert!(read("day17/benches/speed.rs").contains("day17::Solver"));
+        assert!(read("day17/Cargo.toml").contains("name = \"day17\""));
+        assert!(!read("day17/Cargo.toml").contains("priority-queue"));
+        assert_eq!(read("day16/build.rs"), read("day17/build.rs"));
+        assert!(read("input/day17.toml").contains("input = \"day17.txt\""));
+
+        assert!(new_day(&root, &input, "day17").is_err());
+        assert!(new_day(&root, &input, "seventeen").is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,242 @@
+//! `aoc run`: every selected case solved on a pool of threads, then one table of how
+//! they went. Cases running side by side share the cores, so their times are only a
+//! guide, `aoc bench` is the place to measure.
+
+use crate::cli::{RunOptions, Selection};
+use crate::days::Day;
+use crate::{label, select_days};
+use aoc_core::config::{read_config, Part};
+use aoc_core::history::History;
+use aoc_core::visual;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+const HEADER: [&str; 8] = [
+    "day", "part", "mode", "case", "answer", "expected", "result", "elapsed",
+];
+
+pub fn run(selection: &Selection, options: &RunOptions) -> ExitCode {
+    let slow = options.slow;
+    let days = match select_days(&selection.day) {
+        Ok(days) => days,
+        Err(code) => return code,
+    };
+
+    let history = History::load().unwrap_or_else(|e| {
+        println!("answer history not read :: {e}");
+        History::default()
+    });
+
+    let mut failures = 0;
+    let mut cases: Vec<(&Day, Part)> = Vec::new();
+    for day in days {
+        match read_config(day.name) {
+            Ok(parts) => cases.extend(
+                parts
+                    .into_iter()
+                    .filter(|p| selection.includes(p))
+                    .map(|p| (day, p)),
+            ),
+            Err(e) => {
+                println!("{:<8} :: {e}", day.name);
+                failures += 1;
+            }
+        }
+    }
+
+    // frames drawn on the terminal would be mixed up with another case's
+    let jobs = match options.visual.needs_terminal() {
+        true => 1,
+        false => options
+            .jobs
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get())),
+    };
+    let started = Instant::now();
+    let results = in_parallel(&cases, jobs, |(day, part)| {
+        let case = format!("{}-part{}-{}", day.name, part.part, part.name);
+        options.visual.visualiser(&case).and_then(|visualiser| {
+            visual::showing(visualiser, || (day.run)(&part.input, part.part))
+        })
+    });
+    let wall = started.elapsed();
+
+    let mut rows = Vec::new();
+    let mut notes = Vec::new();
+    let (mut passed, mut slow_count) = (0, 0);
+    for ((day, part), result) in cases.iter().zip(results) {
+        let label = label(day, part);
+        let (answer, result, elapsed) = match result {
+            Ok(Ok((answer, timings))) => {
+                if let Some(warning) = history.check_case(day.name, part, &answer) {
+                    notes.push(format!("{label} :: warning, {warning}"));
+                }
+                let result = match answer == part.expected {
+                    true => "pass",
+                    false => "FAIL",
+                };
+                let mut elapsed = format!("{:.2?}", timings.total());
+                if timings.total() > slow {
+                    slow_count += 1;
+                    elapsed += " SLOW";
+                }
+                (answer.to_string(), result, elapsed)
+            }
+            Ok(Err(e)) => {
+                notes.push(format!("{label} <{}> failed :: {e}", part.input));
+                ("-".to_string(), "ERROR", "-".to_string())
+            }
+            Err(panic) => {
+                notes.push(format!("{label} <{}> panicked :: {panic}", part.input));
+                ("-".to_string(), "ERROR", "-".to_string())
+            }
+        };
+
+        match result {
+            "pass" => passed += 1,
+            _ => failures += 1,
+        }
+        rows.push(vec![
+            day.name.to_string(),
+            part.part.to_string(),
+            part.mode.to_string(),
+            part.name.clone(),
+            answer,
+            part.expected.to_string(),
+            result.to_string(),
+            elapsed,
+        ]);
+    }
+
+    // with tsv only the rows go to stdout, for watch to read
+    let say = |text: &str| match options.tsv {
+        true => eprintln!("{text}"),
+        false => println!("{text}"),
+    };
+    match options.tsv {
+        true => rows.iter().for_each(|row| println!("{}", row.join("\t"))),
+        false => print!("{}", table(&HEADER, &rows)),
+    }
+    for note in notes {
+        say(&note);
+    }
+    say(&format!(
+        "{passed} passed, {} failed, {slow_count} slower than {slow:.2?}, in {wall:.2?} on {jobs} thread{}",
+        cases.len() - passed,
+        if jobs == 1 { "" } else { "s" }
+    ));
+
+    if failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Calls `f` on every item from a pool of `jobs` threads, the results in the items' order.
+/// A panic only loses its own item, coming back as the panic's message.
+fn in_parallel<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<Result<R, String>> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(items.iter().map(|_| None).collect::<Vec<_>>());
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(item)))
+                    .map_err(|panic| {
+                        panic
+                            .downcast_ref::<&str>()
+                            .map(|s| s.to_string())
+                            .or_else(|| panic.downcast_ref::<String>().cloned())
+                            .unwrap_or_else(|| "no message".to_string())
+                    });
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every item taken"))
+        .collect()
+}
+
+/// The rows under the header, each column as wide as its widest cell
+pub fn table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths = header.iter().map(|h| h.chars().count()).collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut text = line(header, &widths);
+    text += &line(
+        &widths.iter().map(|&w| "-".repeat(w)).collect::<Vec<_>>(),
+        &widths,
+    );
+    for row in rows {
+        text += &line(row, &widths);
+    }
+    text
+}
+
+fn line<S: AsRef<str>>(cells: &[S], widths: &[usize]) -> String {
+    let padded = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:<width$}", cell.as_ref()))
+        .collect::<Vec<_>>();
+    padded.join("  ").trim_end().to_string() + "\n"
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn keeps_the_order_and_survives_panics() {
+        let items = (0..20).collect::<Vec<u64>>();
+        let results = in_parallel(&items, 4, |&n| {
+            if n == 7 {
+                panic!("seven");
+            }
+            std::thread::sleep(Duration::from_millis(20 - n));
+            n * 2
+        });
+
+        assert_eq!(Err("seven".to_string()), results[7]);
+        for (n, result) in results.iter().enumerate().filter(|(n, _)| *n != 7) {
+            assert_eq!(Ok(n as u64 * 2), *result);
+        }
+        assert!(in_parallel(&Vec::<u8>::new(), 4, |&n| n).is_empty());
+    }
+
+    #[test]
+    fn lines_up_the_columns() {
+        let rows = vec![
+            vec!["day16".to_string(), "7036".to_string(), "pass".to_string()],
+            vec!["day7_v2".to_string(), "-".to_string(), "ERROR".to_string()],
+        ];
+        assert_eq!(
+            "day      answer  result
+-------  ------  ------
+day16    7036    pass
+day7_v2  -       ERROR
+",
+            table(&["day", "answer", "result"], &rows)
+        );
+    }
+}
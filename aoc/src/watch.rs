@@ -0,0 +1,264 @@
+//! `aoc watch dayN`: polls the day's sources, its config and the inputs the config names,
+//! and on every change rebuilds, reruns the day's cases and shows how the answers moved.
+//! The rerun happens in a fresh `aoc run --tsv` so it picks up the rebuilt code.
+
+use crate::summary::table;
+use aoc_core::config::read_config;
+use aoc_core::files::{self, Input};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode, Stdio};
+use std::time::{Duration, SystemTime};
+
+const POLL: Duration = Duration::from_millis(500);
+
+const HEADER: [&str; 7] = [
+    "part", "case", "answer", "expected", "result", "elapsed", "change",
+];
+
+/// When each watched file last changed and how big it was, missing files being left out
+type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+/// One row of `aoc run --tsv`: day, part, mode, case, answer, expected, result, elapsed
+type Row = Vec<String>;
+
+pub fn watch(day: &str) -> ExitCode {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    if !root.join(day).join("src").is_dir() {
+        eprintln!("no {day} crate in {}", root.display());
+        return ExitCode::from(2);
+    }
+
+    let mut seen = Snapshot::new();
+    let mut previous: Option<Vec<Row>> = None;
+    loop {
+        let mut now = snapshot(&watched(root, day));
+        if now == seen {
+            std::thread::sleep(POLL);
+            continue;
+        }
+
+        // give an editor saving several files the chance to finish
+        loop {
+            std::thread::sleep(POLL);
+            let settled = snapshot(&watched(root, day));
+            if settled == now {
+                break;
+            }
+            now = settled;
+        }
+
+        if previous.is_some() {
+            println!("{day} :: changed {}", describe(root, &seen, &now));
+        }
+        seen = now;
+
+        match rebuild_and_run(root, day) {
+            Ok(rows) => {
+                let (table_rows, notes) = compare(previous.as_deref(), &rows);
+                print!("{}", table(&HEADER, &table_rows));
+                for note in notes {
+                    println!("{day} :: {note}");
+                }
+                previous = Some(rows);
+            }
+            Err(e) => println!("{day} :: {e}"),
+        }
+        println!("{day} :: watching {} files, ctrl-c to stop", seen.len());
+    }
+}
+
+/// The day's sources and manifest, its config, and every input file the config names
+fn watched(root: &Path, day: &str) -> BTreeSet<PathBuf> {
+    let mut paths = BTreeSet::new();
+    let crate_dir = root.join(day);
+    collect_files(&crate_dir.join("src"), &mut paths);
+    paths.insert(crate_dir.join("Cargo.toml"));
+    paths.insert(crate_dir.join("build.rs"));
+
+    for config in [format!("{day}.toml"), format!("{day}.config")] {
+        paths.extend(files::find_input(&config));
+    }
+    for part in read_config(day).unwrap_or_default() {
+        if let Input::File(name) = &part.input {
+            if name != files::STDIN {
+                paths.extend(files::find_input(name));
+            }
+        }
+    }
+    paths
+}
+
+fn collect_files(dir: &Path, paths: &mut BTreeSet<PathBuf>) {
+    for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        match path.is_dir() {
+            true => collect_files(&path, paths),
+            false => {
+                paths.insert(path);
+            }
+        }
+    }
+}
+
+fn snapshot(paths: &BTreeSet<PathBuf>) -> Snapshot {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let metadata = std::fs::metadata(path).ok()?;
+            Some((path.clone(), (metadata.modified().ok()?, metadata.len())))
+        })
+        .collect()
+}
+
+/// The files that differ between the snapshots, named from the workspace root
+fn describe(root: &Path, before: &Snapshot, after: &Snapshot) -> String {
+    let changed = before
+        .keys()
+        .chain(after.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|path| before.get(*path) != after.get(*path))
+        .map(|path| {
+            path.strip_prefix(root)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+
+    match changed.len() {
+        0..=3 => changed.join(", "),
+        n => format!("{} and {} more", changed[..3].join(", "), n - 3),
+    }
+}
+
+/// Builds aoc with the profile this watch was built with, then runs the day,
+/// build errors and failed cases showing on stderr as they happen
+fn rebuild_and_run(root: &Path, day: &str) -> Result<Vec<Row>, String> {
+    let cargo = std::env::var("CARGO").unwrap_or("cargo".to_string());
+    let profile: &[&str] = match cfg!(debug_assertions) {
+        true => &[],
+        false => &["--release"],
+    };
+
+    let built = Command::new(&cargo)
+        .current_dir(root)
+        .args(["build", "-q", "-p", "aoc"])
+        .args(profile)
+        .status()
+        .map_err(|e| format!("couldn't run {cargo} :: {e}"))?;
+    if !built.success() {
+        return Err("the build failed, waiting for the next change".to_string());
+    }
+
+    let output = Command::new(&cargo)
+        .current_dir(root)
+        .args(["run", "-q", "-p", "aoc"])
+        .args(profile)
+        .args(["--", "run", day, "--tsv"])
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("couldn't run {cargo} :: {e}"))?;
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.split('\t').map(str::to_string).collect::<Row>())
+        .filter(|row| row.len() == 8)
+        .collect())
+}
+
+/// The rows to show, with how each answer differs from the last run,
+/// and a note for each case that has gone since
+fn compare(previous: Option<&[Row]>, rows: &[Row]) -> (Vec<Row>, Vec<String>) {
+    let case = |row: &Row| (row[1].clone(), row[3].clone());
+    let answers = previous
+        .unwrap_or_default()
+        .iter()
+        .map(|row| (case(row), row[4].clone()))
+        .collect::<BTreeMap<_, _>>();
+
+    let shown = rows
+        .iter()
+        .map(|row| {
+            let change = match (previous, answers.get(&case(row))) {
+                (None, _) => String::new(),
+                (Some(_), None) => "new".to_string(),
+                (Some(_), Some(answer)) if *answer == row[4] => String::new(),
+                (Some(_), Some(answer)) => format!("was {answer}"),
+            };
+            vec![
+                row[1].clone(),
+                row[3].clone(),
+                row[4].clone(),
+                row[5].clone(),
+                row[6].clone(),
+                row[7].clone(),
+                change,
+            ]
+        })
+        .collect();
+
+    let current = rows.iter().map(case).collect::<BTreeSet<_>>();
+    let gone = answers
+        .keys()
+        .filter(|key| !current.contains(key))
+        .map(|(part, name)| format!("part {part} {name} is no longer run"))
+        .collect();
+    (shown, gone)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn row(part: &str, case: &str, answer: &str) -> Row {
+        [
+            "day16", part, "Real", case, answer, "7036", "pass", "3.10ms",
+        ]
+        .map(str::to_string)
+        .to_vec()
+    }
+
+    #[test]
+    fn shows_how_the_answers_moved() {
+        let before = vec![
+            row("1", "real", "7036"),
+            row("2", "real", "45"),
+            row("2", "old", "1"),
+        ];
+        let after = vec![
+            row("1", "real", "7036"),
+            row("2", "real", "64"),
+            row("1", "extra", "9"),
+        ];
+
+        let (first, notes) = compare(None, &before);
+        assert!(first.iter().all(|r| r[6].is_empty()) && notes.is_empty());
+
+        let (shown, notes) = compare(Some(&before), &after);
+        let changes = shown.iter().map(|r| r[6].as_str()).collect::<Vec<_>>();
+        assert_eq!(vec!["", "was 45", "new"], changes);
+        assert_eq!(vec!["part 2 old is no longer run"], notes);
+    }
+
+    #[test]
+    fn notices_changed_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("src/nested")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "mod nested;").unwrap();
+        std::fs::write(dir.join("src/nested/mod.rs"), "").unwrap();
+
+        let mut paths = BTreeSet::new();
+        collect_files(&dir.join("src"), &mut paths);
+        paths.insert(dir.join("missing.txt"));
+        let before = snapshot(&paths);
+        assert_eq!(2, before.len());
+
+        std::fs::write(dir.join("src/nested/mod.rs"), "pub fn f() {}").unwrap();
+        let after = snapshot(&paths);
+        assert_eq!("src/nested/mod.rs", describe(&dir, &before, &after));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,103 @@
+/// What a part produces and what the config expects of it.
+/// Numbers compare by value whatever their width, text compares with the printed form of the other side.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u128),
+    Text(String),
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Signed(v) => v.fmt(f),
+            Answer::Unsigned(v) => v.fmt(f),
+            Answer::Text(v) => v.fmt(f),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Signed(a), Answer::Signed(b)) => a == b,
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Signed(a), Answer::Unsigned(b)) | (Answer::Unsigned(b), Answer::Signed(a)) => {
+                u128::try_from(*a).is_ok_and(|a| a == *b)
+            }
+            (Answer::Text(a), b) | (b, Answer::Text(a)) => *a == b.to_string(),
+        }
+    }
+}
+
+/// Numbers become the narrowest variant that holds them, anything else is text
+impl std::str::FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match (s.parse::<i64>(), s.parse::<u128>()) {
+            (Ok(v), _) => Answer::Signed(v),
+            (_, Ok(v)) => Answer::Unsigned(v),
+            _ => Answer::Text(s.to_string()),
+        })
+    }
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(v: $t) -> Self {
+                Answer::Signed(v.into())
+            }
+        }
+    )*};
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(v: $t) -> Self {
+                Answer::Unsigned(v as u128)
+            }
+        }
+    )*};
+}
+
+from_signed!(i8, i16, i32, i64);
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(v: String) -> Self {
+        Answer::Text(v)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(v: &str) -> Self {
+        Answer::Text(v.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn numbers_compare_across_widths() {
+        assert_eq!(Answer::Signed(42), Answer::Unsigned(42));
+        assert_ne!(Answer::Signed(-1), Answer::Unsigned(u128::MAX));
+        assert_eq!(Answer::from(42u64), "42".parse().unwrap());
+    }
+
+    #[test]
+    fn parses_the_narrowest_variant() {
+        let parse = |s: &str| s.parse::<Answer>().unwrap();
+        assert!(matches!(parse("-17"), Answer::Signed(-17)));
+        assert!(matches!(
+            parse("340282366920938463463374607431768211455"),
+            Answer::Unsigned(u128::MAX)
+        ));
+        assert!(matches!(parse("6,1"), Answer::Text(_)));
+        assert_eq!(Answer::from("6,1"), parse("6,1"));
+    }
+}
@@ -0,0 +1,291 @@
+//! Benchmark results kept between runs in <benchmarks.toml> beside the inputs, so a change can be
+//! checked against how fast each case used to be.
+//! Every run appends a [[run]] table describing the machine and compiler,
+//! followed by one [[result]] table per case it measured, e.g.
+//!
+//! [[run]]
+//! id = 3
+//! time = 1734307200           # seconds since the unix epoch
+//! machine = "box (AMD Ryzen 7 5800X, 16 threads, linux x86_64)"
+//! rustc = "rustc 1.83.0 (90b35a623 2024-11-26)"
+//!
+//! [[result]]
+//! run = 3
+//! day = "day16"
+//! part = 1
+//! case = "real"
+//! parse_ns = 58010
+//! solve_ns = 3460112
+//!
+//! Timings only compare on the machine that took them, so history is kept per machine.
+
+use crate::error::Error;
+use crate::files::{input_exists, input_path, load_full_input_as_string};
+use crate::misc::AResult;
+use crate::toml::{self, quote, Fields};
+use std::time::Duration;
+
+pub const BASELINE_FILE: &str = "benchmarks.toml";
+
+/// Where and with what one set of results was measured
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub id: u64,
+    pub time: u64,
+    pub machine: String,
+    pub rustc: String,
+}
+
+/// How long one case took to parse and solve
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub day: String,
+    pub part: u8,
+    pub case: String,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Sample {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+
+    fn is_case(&self, day: &str, part: u8, case: &str) -> bool {
+        self.day == day && self.part == part && self.case == case
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    pub runs: Vec<Run>,
+    /// each result with the id of the run it belongs to
+    pub results: Vec<(u64, Sample)>,
+}
+
+impl Baseline {
+    /// The stored results, or none at all if nothing has been saved yet
+    pub fn load() -> AResult<Self> {
+        if !input_exists(BASELINE_FILE) {
+            return Ok(Baseline::default());
+        }
+        Self::read(BASELINE_FILE, &load_full_input_as_string(BASELINE_FILE)?)
+    }
+
+    pub fn read(file: &str, text: &str) -> AResult<Self> {
+        let (tables, diagnostics) = toml::parse(file, text);
+        if let Some(first) = diagnostics.into_iter().next() {
+            return Err(first.into());
+        }
+
+        let mut baseline = Baseline::default();
+        for table in &tables {
+            let fields = Fields { file, table };
+            match table.name.as_str() {
+                "run" => baseline.runs.push(Run {
+                    id: fields.number("id")?,
+                    time: fields.number("time")?,
+                    machine: fields.string("machine")?,
+                    rustc: fields.string("rustc")?,
+                }),
+                "result" => baseline.results.push((
+                    fields.number("run")?,
+                    Sample {
+                        day: fields.string("day")?,
+                        part: fields.number("part")?,
+                        case: fields.string("case")?,
+                        parse: Duration::from_nanos(fields.number("parse_ns")?),
+                        solve: Duration::from_nanos(fields.number("solve_ns")?),
+                    },
+                )),
+                "" if table.entries.is_empty() => {}
+                other => {
+                    return Err(Error::config(
+                        file,
+                        Some(table.line),
+                        format!("unknown table '{other}'"),
+                    ))
+                }
+            }
+        }
+        Ok(baseline)
+    }
+
+    /// Appends a run to the baseline file, returning the id it was given
+    pub fn save(&mut self, machine: &str, rustc: &str, samples: &[Sample]) -> AResult<u64> {
+        let path = input_path(BASELINE_FILE);
+        let text = self.record(machine, rustc, samples);
+
+        use std::io::Write;
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(text.as_bytes()))
+            .map_err(|error| Error::Io { path, error })?;
+
+        Ok(self.runs.last().map_or(0, |run| run.id))
+    }
+
+    /// Adds a run and its samples, returning them as the text to append to the file
+    pub fn record(&mut self, machine: &str, rustc: &str, samples: &[Sample]) -> String {
+        let run = Run {
+            id: self
+                .runs
+                .iter()
+                .map(|run| run.id)
+                .max()
+                .map_or(1, |id| id + 1),
+            time: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            machine: machine.to_string(),
+            rustc: rustc.to_string(),
+        };
+
+        let mut text = format!(
+            "\n[[run]]\nid = {}\ntime = {}\nmachine = {}\nrustc = {}\n",
+            run.id,
+            run.time,
+            quote(&run.machine),
+            quote(&run.rustc)
+        );
+        for sample in samples {
+            text += &format!(
+                "\n[[result]]\nrun = {}\nday = {}\npart = {}\ncase = {}\nparse_ns = {}\nsolve_ns = {}\n",
+                run.id,
+                quote(&sample.day),
+                sample.part,
+                quote(&sample.case),
+                sample.parse.as_nanos(),
+                sample.solve.as_nanos()
+            );
+            self.results.push((run.id, sample.clone()));
+        }
+        self.runs.push(run);
+        text
+    }
+
+    /// Earlier results for a case on this machine, oldest first
+    pub fn history(&self, machine: &str, day: &str, part: u8, case: &str) -> Vec<&Sample> {
+        self.results
+            .iter()
+            .filter(|(run, sample)| {
+                sample.is_case(day, part, case)
+                    && self
+                        .runs
+                        .iter()
+                        .any(|r| r.id == *run && r.machine == machine)
+            })
+            .map(|(_, sample)| sample)
+            .collect()
+    }
+}
+
+/// How much slower `now` is than `before`, as a percentage, negative when it got faster
+pub fn slowdown(before: Duration, now: Duration) -> f64 {
+    (now.as_secs_f64() / before.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0) * 100.0
+}
+
+/// One bar per timing, scaled between the fastest and the slowest of them
+pub fn trend(timings: &[Duration]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let (Some(min), Some(max)) = (timings.iter().min(), timings.iter().max()) else {
+        return String::new();
+    };
+    let range = (*max - *min).as_secs_f64();
+
+    timings
+        .iter()
+        .map(|t| match range > 0.0 {
+            true => BARS[((*t - *min).as_secs_f64() / range * 7.0).round() as usize],
+            false => BARS[0],
+        })
+        .collect()
+}
+
+/// Enough about this machine to tell its timings apart from another's
+pub fn machine() -> String {
+    let host = std::fs::read_to_string("/etc/hostname")
+        .ok()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    let cpu = std::fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|info| {
+            info.lines()
+                .find(|line| line.starts_with("model name"))
+                .and_then(|line| line.split_once(':'))
+                .map(|(_, model)| model.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown cpu".to_string());
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+
+    format!(
+        "{host} ({cpu}, {threads} threads, {} {})",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample(case: &str, parse_ns: u64, solve_ns: u64) -> Sample {
+        Sample {
+            day: "day1".to_string(),
+            part: 1,
+            case: case.to_string(),
+            parse: Duration::from_nanos(parse_ns),
+            solve: Duration::from_nanos(solve_ns),
+        }
+    }
+
+    #[test]
+    fn reads_back_what_it_records() {
+        let mut baseline = Baseline::default();
+        let mut text = baseline.record("box \"one\"", "rustc 1.0", &[sample("real", 10, 20)]);
+        text += &baseline.record("box \"one\"", "rustc 1.0", &[sample("real", 15, 25)]);
+        text += &baseline.record("other", "rustc 1.0", &[sample("real", 1, 1)]);
+
+        let read = Baseline::read("benchmarks.toml", &text).unwrap();
+        assert_eq!(baseline, read);
+        assert_eq!(
+            vec![1, 2, 3],
+            read.runs.iter().map(|r| r.id).collect::<Vec<_>>()
+        );
+
+        let history = read.history("box \"one\"", "day1", 1, "real");
+        assert_eq!(
+            vec![Duration::from_nanos(30), Duration::from_nanos(40)],
+            history.iter().map(|s| s.total()).collect::<Vec<_>>()
+        );
+        assert!(read.history("box \"one\"", "day1", 2, "real").is_empty());
+    }
+
+    #[test]
+    fn reports_a_bad_result() {
+        let text = "[[result]]\nrun = 1\nday = \"day1\"\npart = 1\ncase = \"real\"\nparse_ns = 5\n";
+        assert_eq!(
+            "format error in <benchmarks.toml> line 1 :: [[result]] is missing 'solve_ns'",
+            Baseline::read("benchmarks.toml", text)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn measures_slowdown_and_trend() {
+        let ms = Duration::from_millis;
+        assert_eq!(50.0, slowdown(ms(10), ms(15)).round());
+        assert_eq!(-50.0, slowdown(ms(10), ms(5)).round());
+        assert_eq!("▁█▅▁", trend(&[ms(1), ms(9), ms(5), ms(1)]));
+        assert_eq!("▁▁", trend(&[ms(3), ms(3)]));
+        assert_eq!("", trend(&[]));
+    }
+}
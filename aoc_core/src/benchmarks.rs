@@ -0,0 +1,156 @@
+//! The benchmarks behind each day's `benches/speed.rs` (harness = false).
+//! Like the test harness the config is read when they run, and each real case gets
+//! three benchmarks: `part<N>::<case>` from input to answer, then `::parse` and `::solve`
+//! on their own, so a change can be put down to the parser or the algorithm.
+//! A case whose input isn't on this machine is ignored.
+
+use crate::config::{read_config, Mode, Part};
+use crate::files::{self, input_exists, Input};
+use crate::solution::Solution;
+use bencher::{Bencher, TDynBenchFn, TestDesc, TestDescAndFn, TestFn, TestOpts};
+use std::marker::PhantomData;
+use std::process::ExitCode;
+
+/// What part of solving a case is timed
+#[derive(Debug, Clone, Copy)]
+enum Stage {
+    /// input through to answer
+    Whole,
+    Parse,
+    /// solving only, on input parsed once up front
+    Solve,
+}
+
+struct Bench<S> {
+    part: u8,
+    input: Input,
+    stage: Stage,
+    solver: PhantomData<fn() -> S>,
+}
+
+impl<S: Solution> TDynBenchFn for Bench<S> {
+    fn run(&self, b: &mut Bencher) {
+        let input = files::load_input_with(&self.input, S::NORMALISE).expect("an input");
+        match self.stage {
+            Stage::Whole => b.iter(|| std::hint::black_box(S::solve(&input, self.part).unwrap())),
+            Stage::Parse => b.iter(|| std::hint::black_box(S::parse(&input).unwrap())),
+            Stage::Solve => {
+                let parsed = S::parse(&input).expect("a parsed input");
+                b.iter(|| std::hint::black_box(S::solve_parsed(&parsed, self.part).unwrap()))
+            }
+        }
+    }
+}
+
+fn benches<S: Solution + 'static>(part: &Part) -> Vec<TestDescAndFn> {
+    let missing = matches!(&part.input, Input::File(filename) if !input_exists(filename));
+    [
+        (Stage::Whole, ""),
+        (Stage::Parse, "::parse"),
+        (Stage::Solve, "::solve"),
+    ]
+    .into_iter()
+    .map(|(stage, suffix)| TestDescAndFn {
+        desc: TestDesc {
+            name: format!("part{}::{}{suffix}", part.part, part.name).into(),
+            ignore: missing,
+        },
+        testfn: TestFn::DynBenchFn(Box::new(Bench::<S> {
+            part: part.part,
+            input: part.input.clone(),
+            stage,
+            solver: PhantomData,
+        })),
+    })
+    .collect()
+}
+
+/// Benchmark every real case in the day's config, an argument picks them out by name
+pub fn run<S: Solution + 'static>(day: &str) -> ExitCode {
+    let parts = match read_config(day) {
+        Ok(parts) => parts,
+        Err(e) => {
+            eprintln!("{day} :: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let benches = parts
+        .iter()
+        .filter(|p| p.mode == Mode::Real)
+        .flat_map(benches::<S>)
+        .collect::<Vec<_>>();
+    if benches.is_empty() {
+        println!("{day} has no real cases in its config");
+        return ExitCode::SUCCESS;
+    }
+
+    let options = TestOpts {
+        filter: std::env::args().skip(1).find(|arg| *arg != "--bench"),
+        ..TestOpts::default()
+    };
+    match bencher::run_tests_console(&options, benches) {
+        Ok(true) => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::Error;
+    use crate::misc::AResult;
+
+    struct Doubler;
+
+    impl Solution for Doubler {
+        type Input = i64;
+        type Answer = i64;
+
+        fn parse(input: &str) -> AResult<i64> {
+            input
+                .trim()
+                .parse()
+                .map_err(|_| Error::solver("not a number"))
+        }
+
+        fn part1(n: &i64) -> AResult<i64> {
+            Ok(n * 2)
+        }
+
+        fn part2(n: &i64) -> AResult<i64> {
+            Ok(n * 4)
+        }
+    }
+
+    #[test]
+    fn benchmarks_each_stage_of_a_case() {
+        let mut part = Part {
+            name: "real".to_string(),
+            part: 2,
+            mode: Mode::Real,
+            input: Input::Inline("21".to_string()),
+            expected: 84.into(),
+            tags: Vec::new(),
+            notes: None,
+        };
+        let described = |part: &Part| {
+            benches::<Doubler>(part)
+                .into_iter()
+                .map(|b| (b.desc.name.to_string(), b.desc.ignore))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec![
+                ("part2::real".to_string(), false),
+                ("part2::real::parse".to_string(), false),
+                ("part2::real::solve".to_string(), false),
+            ],
+            described(&part)
+        );
+
+        part.input = Input::File("no such input.txt".to_string());
+        assert!(described(&part).iter().all(|(_, ignored)| *ignored));
+    }
+}
@@ -0,0 +1,302 @@
+//! Fetches puzzle inputs and submits answers, logged in with the session cookie
+//! from AOC_SESSION. AOC_BASE_URL points it at another server, such as a local
+//! stand-in for tests, and AOC_YEAR picks the event.
+
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::misc::AResult;
+use std::time::Duration;
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const YEAR_VAR: &str = "AOC_YEAR";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_YEAR: u32 = 2024;
+
+const USER_AGENT: &str = "aoc24 input and answer client";
+
+/// What the server made of a submitted answer
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// answered too recently, with how long is left to wait if the page said
+    RateLimited(Option<String>),
+    /// the part has already been solved, or part 2 isn't open yet
+    WrongLevel,
+    /// a page none of the above matched, kept so it can be shown
+    Unrecognised(String),
+}
+
+impl Verdict {
+    /// Reads the verdict from the page the server answers a submission with
+    pub fn from_page(page: &str) -> Self {
+        let text = page.to_lowercase();
+
+        if text.contains("that's the right answer") {
+            Verdict::Right
+        } else if text.contains("too recently") {
+            let wait = text
+                .split_once(" left to wait")
+                .and_then(|(before, _)| before.rsplit_once("you have "))
+                .map(|(_, wait)| wait.to_string());
+            Verdict::RateLimited(wait)
+        } else if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if text.contains("not the right answer") {
+            Verdict::Wrong
+        } else if text.contains("solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unrecognised(page.trim().to_string())
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Right => f.write_str("right"),
+            Verdict::Wrong => f.write_str("wrong"),
+            Verdict::TooHigh => f.write_str("wrong, too high"),
+            Verdict::TooLow => f.write_str("wrong, too low"),
+            Verdict::RateLimited(Some(wait)) => write!(f, "answered too recently, wait {wait}"),
+            Verdict::RateLimited(None) => f.write_str("answered too recently"),
+            Verdict::WrongLevel => f.write_str("already solved, or not open yet"),
+            Verdict::Unrecognised(page) => write!(f, "unrecognised response :: {page}"),
+        }
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    year: u32,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, year: u32, session: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            year,
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// A client for the server, year and session the environment names
+    pub fn from_env() -> AResult<Self> {
+        let base_url = std::env::var(BASE_URL_VAR).unwrap_or(DEFAULT_BASE_URL.to_string());
+        let unusable = |reason: String| Error::Http {
+            url: base_url.clone(),
+            reason,
+        };
+
+        let session = std::env::var(SESSION_VAR)
+            .map_err(|_| unusable(format!("no session, set {SESSION_VAR}")))?;
+        let year = match std::env::var(YEAR_VAR) {
+            Ok(year) => year
+                .parse()
+                .map_err(|_| unusable(format!("{YEAR_VAR} should be a year, not {year}")))?,
+            Err(_) => DEFAULT_YEAR,
+        };
+        Ok(Client::new(&base_url, year, &session))
+    }
+
+    fn url(&self, day: u32, page: &str) -> String {
+        format!("{}/{}/day/{day}/{page}", self.base_url, self.year)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// The puzzle input for the day, which differs for every session
+    pub fn download(&self, day: u32) -> AResult<String> {
+        let url = self.url(day, "input");
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call();
+        read(&url, response)
+    }
+
+    pub fn submit(&self, day: u32, part: u8, answer: &Answer) -> AResult<Verdict> {
+        let url = self.url(day, "answer");
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ]);
+        read(&url, response).map(|page| Verdict::from_page(&page))
+    }
+}
+
+fn read(url: &str, response: Result<ureq::Response, ureq::Error>) -> AResult<String> {
+    let http_error = |reason: String| Error::Http {
+        url: url.to_string(),
+        reason,
+    };
+
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| http_error(e.to_string())),
+        Err(ureq::Error::Status(status, response)) => {
+            let page = response.into_string().unwrap_or_default();
+            let first_line = page.lines().next().unwrap_or_default().trim();
+            Err(http_error(format!("{status} {first_line}")))
+        }
+        Err(e) => Err(http_error(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    /// A stand-in server giving each response in turn, then handing back the requests it got
+    fn serve(responses: &[(u16, &str)]) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let responses = responses
+            .iter()
+            .map(|(status, body)| (*status, body.to_string()))
+            .collect::<Vec<_>>();
+
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                    request += &line;
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request += &String::from_utf8(content).unwrap();
+                requests.push(request);
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+
+        (url, server)
+    }
+
+    #[test]
+    fn downloads_with_the_session() {
+        let (url, server) = serve(&[(200, "1 2\n3 4\n")]);
+        let client = Client::new(&format!("{url}/"), 2024, "abc123\n");
+
+        assert_eq!("1 2\n3 4\n", client.download(5).unwrap());
+
+        let request = &server.join().unwrap()[0];
+        assert!(request.starts_with("GET /2024/day/5/input HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=abc123\r\n"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, server) = serve(&[
+            (
+                200,
+                "<p>That's the right answer! You are one gold star closer.</p>",
+            ),
+            (
+                200,
+                "<p>That's not the right answer; your answer is too low.</p>",
+            ),
+        ]);
+        let client = Client::new(&url, 2023, "abc123");
+
+        assert_eq!(
+            Ok(Verdict::Right),
+            client
+                .submit(16, 2, &Answer::Signed(45))
+                .map_err(|e| e.to_string())
+        );
+        assert_eq!(
+            Verdict::TooLow,
+            client.submit(16, 2, &"6,1".into()).unwrap()
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2023/day/16/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=45"));
+        assert!(requests[1].ends_with("\r\n\r\nlevel=2&answer=6%2C1"));
+    }
+
+    #[test]
+    fn reports_refused_requests() {
+        let (url, server) = serve(&[(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )]);
+        let client = Client::new(&url, 2024, "expired");
+
+        assert_eq!(
+            format!("<{url}/2024/day/1/input> :: 400 Puzzle inputs differ by user.  Please log in to get your puzzle input."),
+            client.download(1).unwrap_err().to_string()
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn reads_every_verdict() {
+        let verdict = |page: &str| {
+            Verdict::from_page(&format!("<main><article><p>{page}</p></article></main>"))
+        };
+
+        assert_eq!(Verdict::Right, verdict("That's the right answer!"));
+        assert_eq!(
+            Verdict::Wrong,
+            verdict("That's not the right answer. If you're stuck...")
+        );
+        assert_eq!(
+            Verdict::TooHigh,
+            verdict("That's not the right answer; your answer is too high.")
+        );
+        assert_eq!(
+            Verdict::RateLimited(Some("4m 52s".to_string())),
+            verdict("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 52s left to wait.")
+        );
+        assert_eq!(
+            Verdict::WrongLevel,
+            verdict("You don't seem to be solving the right level.  Did you already complete it?")
+        );
+        assert!(matches!(verdict("Maintenance"), Verdict::Unrecognised(_)));
+    }
+}
@@ -0,0 +1,813 @@
+use crate::answer::Answer;
+use crate::error::{Diagnostic, Error};
+use crate::files::{input_exists, load_full_input_as_string, write_input, Input};
+use crate::misc::{puzzle_day, puzzle_input_name, AResult};
+use crate::toml::{self, Entry, Table, Value};
+use std::path::PathBuf;
+
+/// One case from the config: an input for a part and the answer it should give
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub name: String,
+    pub part: u8,
+    pub mode: Mode,
+    pub input: Input,
+    pub expected: Answer,
+    pub tags: Vec<String>,
+    pub notes: Option<String>,
+}
+
+/// The real test information is relegated to a config file
+/// so that the code can be shared without revealing it
+/// as per the rules of AoC 24
+/// Each day has a <dayX.toml> holding one [[case]] table per input, e.g.
+///
+/// [[case]]
+/// name = "example"
+/// part = 1
+/// mode = "test"
+/// input = "day1-test.txt"     # or the text itself, input_text = """ ... """
+/// expected = 17               # or a string such as "6,1"
+/// tags = ["example"]          # optional
+/// notes = "from the puzzle"   # optional
+///
+/// Days without a <dayX.toml> fall back to the older <dayX.config>, see Checker::legacy_lines
+pub fn read_config(day: &str) -> AResult<Vec<Part>> {
+    let (parts, diagnostics) = load_config(day, false)?;
+    match diagnostics.into_iter().next() {
+        Some(first) => Err(first.into()),
+        None => Ok(parts),
+    }
+}
+
+/// Everything wrong with the day's config, including inputs it names that don't exist,
+/// rather than stopping at the first problem
+pub fn check_config(day: &str) -> AResult<Vec<Diagnostic>> {
+    Ok(load_config(day, true)?.1)
+}
+
+fn load_config(day: &str, check_inputs: bool) -> AResult<(Vec<Part>, Vec<Diagnostic>)> {
+    let toml_name = format!("{day}.toml");
+    let is_toml = input_exists(&toml_name);
+    let filename = match is_toml {
+        true => toml_name,
+        false => format!("{day}.config"),
+    };
+
+    let input = load_full_input_as_string(&filename)?;
+    let mut checker = Checker {
+        file: &filename,
+        check_inputs,
+        diagnostics: Vec::new(),
+    };
+    let parts = match is_toml {
+        true => checker.cases(&input),
+        false => checker.legacy_lines(&input),
+    };
+    Ok((parts, checker.diagnostics))
+}
+
+/// Reads cases while collecting every problem, a case with any problem is left out
+struct Checker<'a> {
+    file: &'a str,
+    check_inputs: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker<'_> {
+    fn report(&mut self, line: usize, column: usize, reason: impl Into<String>) {
+        self.diagnostics.push(Diagnostic {
+            file: self.file.to_string(),
+            line,
+            column,
+            reason: reason.into(),
+        });
+    }
+
+    fn report_value(&mut self, entry: &Entry, reason: impl Into<String>) {
+        self.report(entry.line, entry.value_column, reason);
+    }
+
+    fn cases(&mut self, input: &str) -> Vec<Part> {
+        let (tables, diagnostics) = toml::parse(self.file, input);
+        self.diagnostics.extend(diagnostics);
+
+        let mut parts: Vec<Part> = Vec::new();
+        for table in tables {
+            match table.name.as_str() {
+                "" => {
+                    for entry in &table.entries {
+                        self.report(entry.line, entry.column, "keys must belong to a [[case]]");
+                    }
+                    continue;
+                }
+                "case" => {}
+                other => {
+                    let reason = format!("unknown table [{other}], expected [[case]]");
+                    self.report(table.line, table.column, reason);
+                    continue;
+                }
+            }
+
+            let Some(part) = self.case(&table) else {
+                continue;
+            };
+            if parts.iter().any(|p| p.name == part.name) {
+                let reason = format!("duplicate case name '{}'", part.name);
+                self.report(table.line, table.column, reason);
+                continue;
+            }
+            parts.push(part);
+        }
+
+        parts
+    }
+
+    fn case(&mut self, table: &Table) -> Option<Part> {
+        let reported = self.diagnostics.len();
+        let mut fields = CaseFields::default();
+
+        for entry in &table.entries {
+            match entry.key.as_str() {
+                "name" => fields.name = self.string(entry),
+                "mode" => {
+                    fields.mode = self.string(entry).and_then(|mode| {
+                        mode.parse()
+                            .map_err(|e: String| self.report_value(entry, e))
+                            .ok()
+                    })
+                }
+                "input" => {
+                    let filename = self.string(entry);
+                    if let Some(filename) = &filename {
+                        self.check_input(entry.line, entry.value_column, filename);
+                    }
+                    fields.input = self.one_input(entry, fields.input, filename.map(Input::File));
+                }
+                "input_text" => {
+                    let text = self.string(entry).map(Input::Inline);
+                    fields.input = self.one_input(entry, fields.input, text);
+                }
+                "notes" => fields.notes = self.string(entry),
+                "part" => {
+                    fields.part = match &entry.value {
+                        Value::Integer(raw) => {
+                            self.part_number(entry.line, entry.value_column, raw)
+                        }
+                        other => {
+                            let reason =
+                                format!("'part' should be an integer, not {}", other.kind());
+                            self.report_value(entry, reason);
+                            None
+                        }
+                    }
+                }
+                "expected" => {
+                    fields.expected = match &entry.value {
+                        Value::Integer(raw) => match raw.parse::<Answer>() {
+                            Ok(Answer::Text(_)) => {
+                                self.report_value(entry, format!("{raw} doesn't fit in an answer"));
+                                None
+                            }
+                            Ok(answer) => Some(answer),
+                        },
+                        Value::String(s) => Some(Answer::Text(s.clone())),
+                        Value::Array(_) | Value::Other(_) => {
+                            self.report_value(entry, "'expected' should be a number or a string");
+                            None
+                        }
+                    }
+                }
+                "tags" => match &entry.value {
+                    Value::Array(values)
+                        if values.iter().all(|v| matches!(v, Value::String(_))) =>
+                    {
+                        fields.tags = values
+                            .iter()
+                            .filter_map(|v| match v {
+                                Value::String(tag) => Some(tag.clone()),
+                                _ => None,
+                            })
+                            .collect()
+                    }
+                    _ => self.report_value(entry, "'tags' should be an array of strings"),
+                },
+                other => {
+                    let reason = format!("unknown key '{other}'");
+                    self.report(entry.line, entry.column, reason);
+                }
+            }
+        }
+
+        let present = [
+            ("name", fields.name.is_some()),
+            ("part", fields.part.is_some()),
+            ("mode", fields.mode.is_some()),
+            ("input", fields.input.is_some()),
+            ("expected", fields.expected.is_some()),
+        ];
+        // only complain about keys that are absent, not ones already reported as bad
+        let written = |key: &str| {
+            let alternative = if key == "input" { "input_text" } else { key };
+            table
+                .entries
+                .iter()
+                .any(|e| e.key == key || e.key == alternative)
+        };
+        for (key, _) in present.iter().filter(|(_, present)| !present) {
+            if !written(key) {
+                let reason = format!("case is missing '{key}'");
+                self.report(table.line, table.column, reason);
+            }
+        }
+
+        if self.diagnostics.len() > reported {
+            return None;
+        }
+
+        Some(Part {
+            name: fields.name?,
+            part: fields.part?,
+            mode: fields.mode?,
+            input: fields.input?,
+            expected: fields.expected?,
+            tags: fields.tags,
+            notes: fields.notes,
+        })
+    }
+
+    fn string(&mut self, entry: &Entry) -> Option<String> {
+        match &entry.value {
+            Value::String(s) => Some(s.clone()),
+            other => {
+                let reason = format!("'{}' should be a string, not {}", entry.key, other.kind());
+                self.report_value(entry, reason);
+                None
+            }
+        }
+    }
+
+    /// a case reads either a file or inline text, never both
+    fn one_input(
+        &mut self,
+        entry: &Entry,
+        existing: Option<Input>,
+        input: Option<Input>,
+    ) -> Option<Input> {
+        if existing.is_some() {
+            self.report(
+                entry.line,
+                entry.column,
+                "a case takes either 'input' or 'input_text'",
+            );
+        }
+        input
+    }
+
+    fn part_number(&mut self, line: usize, column: usize, raw: &str) -> Option<u8> {
+        match raw.parse::<u8>() {
+            Ok(part @ 1..=2) => Some(part),
+            _ => {
+                self.report(line, column, format!("part should be 1 or 2, not {raw}"));
+                None
+            }
+        }
+    }
+
+    fn check_input(&mut self, line: usize, column: usize, filename: &str) {
+        if self.check_inputs && !input_exists(filename) {
+            self.report(line, column, format!("input file <{filename}> not found"));
+        }
+    }
+
+    /// The original format: [part, mode, input filename, expected result] one per line
+    /// e.g. 1,t,day1-test.txt,17
+    /// blank lines are ignored and lines starting with // are disabled
+    /// The cases are named after their mode, a repeated part and mode gets a count, e.g. test-2
+    fn legacy_lines(&mut self, input: &str) -> Vec<Part> {
+        let mut parts: Vec<Part> = Vec::new();
+
+        for (n, line) in input.lines().enumerate().map(|(n, l)| (n + 1, l)) {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with("//") {
+                continue;
+            }
+
+            let fields = trimmed.split(',').map(|f| f.trim()).collect::<Vec<&str>>();
+            let column = |field: &str| column_of(line, field);
+
+            if fields.len() != 4 {
+                let reason = format!("expected 4 fields but found {}", fields.len());
+                self.report(n, column(trimmed), reason);
+                continue;
+            }
+
+            let reported = self.diagnostics.len();
+            let part = self.part_number(n, column(fields[0]), fields[0]);
+            let mode = fields[1]
+                .parse::<Mode>()
+                .map_err(|e| self.report(n, column(fields[1]), e))
+                .ok();
+            self.check_input(n, column(fields[2]), fields[2]);
+            let expected = self.legacy_answer(n, column(fields[3]), fields[3]);
+
+            if let (Some(part), Some(mode), Some(expected), true) =
+                (part, mode, expected, self.diagnostics.len() == reported)
+            {
+                // a second line for the same part and mode is told apart by a count
+                let name = mode.to_string().to_lowercase();
+                let name = match parts
+                    .iter()
+                    .filter(|p| p.part == part && p.mode == mode)
+                    .count()
+                {
+                    0 => name,
+                    earlier => format!("{name}-{}", earlier + 1),
+                };
+                parts.push(Part {
+                    name,
+                    part,
+                    mode,
+                    input: Input::File(fields[2].to_string()),
+                    expected,
+                    tags: Vec::new(),
+                    notes: None,
+                });
+            }
+        }
+
+        parts
+    }
+
+    /// Unquoted, so a number is only text when it is too wide for an answer, which is a mistake
+    fn legacy_answer(&mut self, line: usize, column: usize, raw: &str) -> Option<Answer> {
+        let digits = raw.strip_prefix('-').unwrap_or(raw);
+        let reason = match raw.parse::<Answer>() {
+            _ if raw.is_empty() => "missing the expected answer".to_string(),
+            Ok(Answer::Text(_))
+                if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                format!("{raw} doesn't fit in an answer")
+            }
+            Ok(answer) => return Some(answer),
+        };
+        self.report(line, column, reason);
+        None
+    }
+}
+
+/// 1-based char column of `field`, which must be a slice of `line`
+fn column_of(line: &str, field: &str) -> usize {
+    let offset = field.as_ptr() as usize - line.as_ptr() as usize;
+    line[..offset].chars().count() + 1
+}
+
+#[derive(Default)]
+struct CaseFields {
+    name: Option<String>,
+    part: Option<u8>,
+    mode: Option<Mode>,
+    input: Option<Input>,
+    expected: Option<Answer>,
+    tags: Vec<String>,
+    notes: Option<String>,
+}
+
+/// Makes `answer` the expected answer of each real case of `part`, adding a case
+/// if there are none, returning the file written. Comments and layout are kept.
+pub fn record_answer(day: &str, part: u8, answer: &Answer) -> AResult<PathBuf> {
+    let toml_name = format!("{day}.toml");
+    let legacy_name = format!("{day}.config");
+    let input = puzzle_day(day).map_or(format!("{day}.txt"), puzzle_input_name);
+
+    let (filename, text) = match input_exists(&legacy_name) && !input_exists(&toml_name) {
+        true => {
+            let text = load_full_input_as_string(&legacy_name)?;
+            let text = record_in_legacy(&legacy_name, &text, part, answer, &input)?;
+            (legacy_name, text)
+        }
+        false => {
+            let text = match input_exists(&toml_name) {
+                true => load_full_input_as_string(&toml_name)?,
+                false => String::new(),
+            };
+            let text = record_in_toml(&toml_name, &text, part, answer, &input)?;
+            (toml_name, text)
+        }
+    };
+
+    write_input(&filename, &text)
+}
+
+fn record_in_toml(
+    file: &str,
+    text: &str,
+    part: u8,
+    answer: &Answer,
+    input: &str,
+) -> AResult<String> {
+    let (tables, diagnostics) = toml::parse(file, text);
+    if let Some(first) = diagnostics.into_iter().next() {
+        return Err(first.into());
+    }
+
+    let literal = match answer {
+        Answer::Text(text) => toml::quote(text),
+        number => number.to_string(),
+    };
+    fn string(entry: &Entry) -> Option<&str> {
+        match &entry.value {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+    let is_real_case = |table: &&Table| {
+        let value = |key| table.entries.iter().find(|e| e.key == key);
+        table.name == "case"
+            && value("mode").and_then(string).and_then(|m| m.parse().ok()) == Some(Mode::Real)
+            && value("part").map(|e| &e.value) == Some(&Value::Integer(part.to_string()))
+    };
+
+    let cases = tables.iter().filter(is_real_case).collect::<Vec<_>>();
+    let mut text = text.trim_end_matches('\n').to_string();
+
+    // working from the end keeps the positions of earlier cases right
+    for table in cases.iter().rev() {
+        match table.entries.iter().find(|e| e.key == "expected") {
+            Some(entry) => text.replace_range(entry.span.clone(), &literal),
+            None if !text
+                .lines()
+                .nth(table.line - 1)
+                .is_some_and(|l| l.trim_start().starts_with("[[")) =>
+            {
+                return Err(Error::config(
+                    file,
+                    Some(table.line),
+                    "the case has no [[case]] header to add 'expected' under, add it by hand",
+                ))
+            }
+            None => {
+                let header_end = text
+                    .match_indices('\n')
+                    .nth(table.line - 1)
+                    .map_or(text.len(), |(i, _)| i);
+                text.insert_str(header_end, &format!("\nexpected = {literal}"));
+            }
+        }
+    }
+
+    if cases.is_empty() {
+        let taken = |name: &str| {
+            tables
+                .iter()
+                .flat_map(|t| t.entries.iter())
+                .any(|e| e.key == "name" && string(e) == Some(name))
+        };
+        let name = match taken("real") {
+            true => format!("real-part{part}"),
+            false => "real".to_string(),
+        };
+        if !text.is_empty() {
+            text += "\n\n";
+        }
+        text += &format!(
+            "[[case]]\nname = {}\npart = {part}\nmode = \"real\"\ninput = {}\nexpected = {literal}",
+            toml::quote(&name),
+            toml::quote(input)
+        );
+    }
+    text.push('\n');
+    Ok(text)
+}
+
+fn record_in_legacy(
+    file: &str,
+    text: &str,
+    part: u8,
+    answer: &Answer,
+    input: &str,
+) -> AResult<String> {
+    let answer = answer.to_string();
+    if answer.contains(',') {
+        return Err(Error::config(
+            file,
+            None,
+            format!("{answer} can't be written to a .config, move the day to a .toml"),
+        ));
+    }
+
+    let mut found = false;
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let fields = line.trim().split(',').map(str::trim).collect::<Vec<_>>();
+        let matches = !line.trim().starts_with("//")
+            && fields.len() == 4
+            && fields[0] == part.to_string()
+            && fields[1].parse::<Mode>() == Ok(Mode::Real);
+
+        match matches {
+            true => {
+                found = true;
+                lines.push(format!(
+                    "{},{},{},{answer}",
+                    fields[0], fields[1], fields[2]
+                ));
+            }
+            false => lines.push(line.to_string()),
+        }
+    }
+    if !found {
+        lines.push(format!("{part},r,{input},{answer}"));
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// provided for tests and benchmarks only
+pub fn read_test_io(part: u8, mode: Mode) -> AResult<Part> {
+    let day = std::env::var("CARGO_PKG_NAME").unwrap();
+    read_config(&day)?
+        .iter()
+        .find(|p| p.part == part && p.mode == mode)
+        .map(|c| Ok(c.clone()))
+        .unwrap_or(Err(Error::config(
+            &format!("{day} config"),
+            None,
+            format!("no entries found for part {part} in {mode} mode"),
+        )))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Test,
+    Real,
+}
+
+impl std::str::FromStr for Mode {
+    type Err = String;
+    fn from_str(input: &str) -> Result<Mode, Self::Err> {
+        match input {
+            "r" | "real" => Ok(Mode::Real),
+            "t" | "test" => Ok(Mode::Test),
+            _ => Err(format!("unknown mode {input}")),
+        }
+    }
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check(text: &str) -> (Vec<Part>, Vec<String>) {
+        let mut checker = Checker {
+            file: "day1.toml",
+            check_inputs: false,
+            diagnostics: Vec::new(),
+        };
+        let parts = checker.cases(text);
+        let diagnostics = checker.diagnostics.iter().map(|d| d.to_string()).collect();
+        (parts, diagnostics)
+    }
+
+    fn cases(text: &str) -> AResult<Vec<Part>> {
+        match check(text) {
+            (parts, diagnostics) if diagnostics.is_empty() => Ok(parts),
+            (_, diagnostics) => Err(Error::solver(diagnostics.join("\n"))),
+        }
+    }
+
+    #[test]
+    fn reads_a_case() {
+        let parts = cases(
+            r#"
+[[case]]
+name = "example"
+part = 2
+mode = "test"
+input = "day1, with a comma.txt"
+expected = 17
+tags = ["example"]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![Part {
+                name: "example".to_string(),
+                part: 2,
+                mode: Mode::Test,
+                input: Input::File("day1, with a comma.txt".to_string()),
+                expected: Answer::Signed(17),
+                tags: vec!["example".to_string()],
+                notes: None,
+            }],
+            parts
+        );
+    }
+
+    #[test]
+    fn reads_inline_input() {
+        let parts = cases(
+            r#"
+[[case]]
+name = "example"
+part = 1
+mode = "test"
+input_text = """
+125 17
+"""
+expected = 55312
+"#,
+        )
+        .unwrap();
+        assert_eq!(Input::Inline("125 17\n".to_string()), parts[0].input);
+
+        let (_, diagnostics) = check(
+            "[[case]]\nname = \"a\"\npart = 1\nmode = \"test\"\ninput = \"a.txt\"\ninput_text = \"1\"\nexpected = 1\n",
+        );
+        assert_eq!(
+            vec!["<day1.toml:6:1> :: a case takes either 'input' or 'input_text'"],
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn reads_wide_and_text_answers() {
+        let case = |expected: &str| {
+            let text = format!(
+                "[[case]]\nname = \"a\"\npart = 1\nmode = \"real\"\ninput = \"a.txt\"\nexpected = {expected}\n"
+            );
+            cases(&text).map(|parts| parts[0].expected.clone())
+        };
+
+        assert!(matches!(case("\"6,1\""), Ok(Answer::Text(t)) if t == "6,1"));
+        assert!(matches!(
+            case("18446744073709551616"),
+            Ok(Answer::Unsigned(_))
+        ));
+        assert!(case("340282366920938463463374607431768211456").is_err());
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let (parts, diagnostics) = check(
+            r#"[[case]]
+name = "a"
+part = 3
+colour = "red"
+mode = "often"
+
+[[case]]
+name = "b"
+part = 1
+mode = "test"
+input = "b.txt"
+expected = 4
+"#,
+        );
+
+        assert_eq!(
+            vec![
+                "<day1.toml:3:8> :: part should be 1 or 2, not 3",
+                "<day1.toml:4:1> :: unknown key 'colour'",
+                "<day1.toml:5:8> :: unknown mode often",
+                "<day1.toml:1:1> :: case is missing 'input'",
+                "<day1.toml:1:1> :: case is missing 'expected'",
+            ],
+            diagnostics
+        );
+        assert_eq!(vec!["b"], parts.iter().map(|p| &p.name).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reports_legacy_fields() {
+        let mut checker = Checker {
+            file: "day1.config",
+            check_inputs: false,
+            diagnostics: Vec::new(),
+        };
+        let parts = checker.legacy_lines(
+            "1, t, a.txt, 17\n// 9, t\n3, x, b.txt, 4\n2, r\n1, r, a.txt,\n1, r, a.txt, 340282366920938463463374607431768211456\n",
+        );
+
+        assert_eq!(1, parts.len());
+        assert_eq!(
+            vec![
+                "<day1.config:3:1> :: part should be 1 or 2, not 3",
+                "<day1.config:3:4> :: unknown mode x",
+                "<day1.config:4:1> :: expected 4 fields but found 2",
+                "<day1.config:5:13> :: missing the expected answer",
+                "<day1.config:6:14> :: 340282366920938463463374607431768211456 doesn't fit in an answer",
+            ],
+            checker
+                .diagnostics
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn names_repeated_legacy_lines_apart() {
+        let mut checker = Checker {
+            file: "day1.config",
+            check_inputs: false,
+            diagnostics: Vec::new(),
+        };
+        let parts = checker
+            .legacy_lines("1,t,a.txt,1\n1,t,b.txt,6,1\n1,t,b.txt,x\n2,t,a.txt,2\n1,t,c.txt,3\n");
+
+        assert_eq!(
+            vec![("test", 1), ("test-2", 1), ("test", 2), ("test-3", 1)],
+            parts
+                .iter()
+                .map(|p| (p.name.as_str(), p.part))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Answer::Text("x".to_string()), parts[1].expected);
+        assert_eq!(1, checker.diagnostics.len());
+    }
+
+    #[test]
+    fn records_answers_in_place() {
+        let text = r#"# day 1
+[[case]]
+name = "real"
+part = 1
+mode = "real"
+input = "day1.txt"
+expected = 0 # a guess
+
+[[case]]
+name = "real-2"
+part = 2
+mode = "real"
+input = "day1.txt"
+expected = "not \" yet"
+"#;
+        let answer = |text| record_in_toml("day1.toml", text, 1, &Answer::Signed(17), "day1.txt");
+        assert_eq!(
+            text.replace("expected = 0 # a guess", "expected = 17 # a guess"),
+            answer(text).unwrap()
+        );
+
+        let text = answer(text).unwrap();
+        let recorded = record_in_toml("day1.toml", &text, 2, &"6,1".into(), "day1.txt").unwrap();
+        assert!(recorded.ends_with("expected = \"6,1\"\n"));
+        assert_eq!(
+            "17 6,1",
+            cases(&recorded)
+                .unwrap()
+                .iter()
+                .map(|p| p.expected.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+    }
+
+    #[test]
+    fn records_answers_for_new_cases() {
+        let text = "[[case]]\nname = \"real\"\npart = 1\nmode = \"real\"\ninput = \"day1.txt\"\n";
+        let recorded =
+            record_in_toml("day1.toml", text, 1, &Answer::Signed(5), "day1.txt").unwrap();
+        assert_eq!(
+            5,
+            cases(&recorded).unwrap()[0]
+                .expected
+                .to_string()
+                .parse()
+                .unwrap()
+        );
+
+        let recorded = record_in_toml("day1.toml", &recorded, 2, &Answer::Signed(9), "day1.txt");
+        let parts = cases(&recorded.unwrap()).unwrap();
+        assert_eq!(("real-part2", 2), (parts[1].name.as_str(), parts[1].part));
+
+        let parts =
+            cases(&record_in_toml("day1.toml", "", 1, &Answer::Signed(3), "day1.txt").unwrap());
+        assert_eq!(Input::File("day1.txt".to_string()), parts.unwrap()[0].input);
+    }
+
+    #[test]
+    fn records_answers_in_legacy_configs() {
+        let text = "1,t,day1-test.txt,17\n// 1,r,day1.txt,0\n1, r ,day1.txt,0\n";
+        assert_eq!(
+            "1,t,day1-test.txt,17\n// 1,r,day1.txt,0\n1,r,day1.txt,42\n2,r,day1.txt,7\n",
+            record_in_legacy(
+                "day1.config",
+                &record_in_legacy("day1.config", text, 1, &Answer::Signed(42), "day1.txt").unwrap(),
+                2,
+                &Answer::Signed(7),
+                "day1.txt"
+            )
+            .unwrap()
+        );
+        assert!(record_in_legacy("day1.config", text, 1, &"6,1".into(), "day1.txt").is_err());
+    }
+}
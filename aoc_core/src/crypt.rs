@@ -0,0 +1,152 @@
+//! Real inputs and answers must not be published, as per the rules of AoC,
+//! but encrypted they can be committed with the code.
+//! An encrypted file is the plain file sealed with XChaCha20-Poly1305, so any change to it
+//! is caught, laid out as MAGIC, a random 24 byte nonce, then the ciphertext.
+//! files reads encrypted inputs and configs as if they were plain, given the key from
+//! AOC_KEY (64 hex digits) or the file named by AOC_KEY_FILE.
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+
+pub const KEY_VAR: &str = "AOC_KEY";
+pub const KEY_FILE_VAR: &str = "AOC_KEY_FILE";
+
+/// Encrypted files sit next to where the plain file would be, with this added to the name
+pub const EXTENSION: &str = ".enc";
+
+const MAGIC: &[u8] = b"aoc-encrypted-1\n";
+const NONCE_LEN: usize = 24;
+
+/// A 256 bit key, written as 64 hex digits
+#[derive(Clone, PartialEq)]
+pub struct Key([u8; 32]);
+
+// keep the key out of any log it might be printed to
+impl std::fmt::Debug for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Key(..)")
+    }
+}
+
+impl std::fmt::Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
+
+impl std::str::FromStr for Key {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.len() != 64 || !s.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err("a key should be 64 hex digits".to_string());
+        }
+
+        let mut key = [0; 32];
+        for (n, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[n * 2..n * 2 + 2], 16).unwrap();
+        }
+        Ok(Key(key))
+    }
+}
+
+impl Key {
+    pub fn generate() -> Self {
+        Key(XChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    /// The key from AOC_KEY, or else from the file AOC_KEY_FILE names
+    pub fn from_env() -> Result<Self, String> {
+        if let Ok(key) = std::env::var(KEY_VAR) {
+            return key.parse().map_err(|e| format!("{KEY_VAR} :: {e}"));
+        }
+
+        match std::env::var_os(KEY_FILE_VAR) {
+            Some(path) => std::fs::read_to_string(&path)
+                .map_err(|e| format!("<{}> :: {e}", path.to_string_lossy()))?
+                .parse()
+                .map_err(|e| format!("<{}> :: {e}", path.to_string_lossy())),
+            None => Err(format!("no key, set {KEY_VAR} or {KEY_FILE_VAR}")),
+        }
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&self.0.into())
+    }
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+pub fn encrypt(key: &Key, plain: &[u8]) -> Vec<u8> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let payload = Payload {
+        msg: plain,
+        aad: MAGIC,
+    };
+    let sealed = key
+        .cipher()
+        .encrypt(&nonce, payload)
+        .expect("encrypting into memory");
+
+    [MAGIC, nonce.as_slice(), &sealed].concat()
+}
+
+pub fn decrypt(key: &Key, data: &[u8]) -> Result<Vec<u8>, String> {
+    let sealed = data
+        .strip_prefix(MAGIC)
+        .ok_or_else(|| "not an encrypted file".to_string())?;
+    if sealed.len() < NONCE_LEN {
+        return Err("the encrypted file is truncated".to_string());
+    }
+
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let payload = Payload {
+        msg: ciphertext,
+        aad: MAGIC,
+    };
+    key.cipher()
+        .decrypt(XNonce::from_slice(nonce), payload)
+        .map_err(|_| {
+            "could not be decrypted, either the key is wrong or the file was altered".to_string()
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decrypts_what_it_encrypts() {
+        let key = Key::generate();
+        let sealed = encrypt(&key, b"1,t,day1-test.txt,17");
+
+        assert!(is_encrypted(&sealed));
+        assert_ne!(encrypt(&key, b"1,t,day1-test.txt,17"), sealed);
+        assert_eq!(Ok(b"1,t,day1-test.txt,17".to_vec()), decrypt(&key, &sealed));
+    }
+
+    #[test]
+    fn rejects_a_wrong_key_or_an_altered_file() {
+        let key = Key::generate();
+        let mut sealed = encrypt(&key, b"secret");
+
+        assert!(decrypt(&Key::generate(), &sealed).is_err());
+        *sealed.last_mut().unwrap() ^= 1;
+        assert!(decrypt(&key, &sealed).is_err());
+        assert!(decrypt(&key, &sealed[..MAGIC.len() + 3]).is_err());
+        assert!(decrypt(&key, b"secret").is_err());
+    }
+
+    #[test]
+    fn reads_keys_as_hex() {
+        let key = Key::generate();
+        assert_eq!(Ok(key.clone()), key.to_string().parse());
+        assert_eq!(Ok(key.clone()), format!(" {key}\n").parse());
+        assert!("abc".parse::<Key>().is_err());
+        assert!("zz".repeat(32).parse::<Key>().is_err());
+        assert_eq!("Key(..)", format!("{key:?}"));
+    }
+}
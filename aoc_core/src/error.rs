@@ -0,0 +1,234 @@
+use std::path::PathBuf;
+
+/// Everything that can go wrong between locating an input and producing an answer
+#[derive(Debug)]
+pub enum Error {
+    /// the input or config file could not be opened or read
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// the file was read but is not text, or is encrypted and could not be decrypted
+    Unreadable { path: PathBuf, reason: String },
+    /// the input or config file is in none of the places inputs are looked for
+    NotFound { name: String, tried: Vec<PathBuf> },
+    /// the puzzle server could not be reached or turned the request down
+    Http { url: String, reason: String },
+    /// the day config is malformed
+    Config {
+        file: String,
+        line: Option<usize>,
+        column: Option<usize>,
+        reason: String,
+    },
+    /// the puzzle input is malformed
+    Parse(ParseError),
+    /// the input parsed but the solver could not produce an answer
+    Solver(String),
+}
+
+/// One problem found in a config file, several can be collected from a single read
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub file: String,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in chars
+    pub column: usize,
+    pub reason: String,
+}
+
+/// Points at the exact piece of the input that could not be understood
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in chars
+    pub column: usize,
+    /// the offending text
+    pub text: String,
+    /// the full line containing the offending text
+    pub line_text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    /// `fragment` must be a slice of `input`, its position is recovered from the pointers.
+    /// Anything else is reported against the end of the input.
+    pub fn at(input: &str, fragment: &str, reason: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + fragment.len() <= input.len())
+            .unwrap_or(input.len());
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Self {
+            file: None,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: fragment.to_string(),
+            line_text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// for errors that are about something missing rather than something present
+    pub fn at_end(input: &str, reason: impl Into<String>) -> Self {
+        Self::at(input, &input[input.len()..], reason)
+    }
+}
+
+impl Error {
+    pub fn solver(reason: impl Into<String>) -> Self {
+        Error::Solver(reason.into())
+    }
+
+    pub fn config(file: &str, line: Option<usize>, reason: impl Into<String>) -> Self {
+        Error::Config {
+            file: file.to_string(),
+            line,
+            column: None,
+            reason: reason.into(),
+        }
+    }
+
+    /// parsers only see the text, the caller knows which file it came from
+    pub fn in_file(self, filename: &str) -> Self {
+        match self {
+            Error::Parse(mut e) if e.file.is_none() => {
+                e.file = Some(filename.to_string());
+                Error::Parse(e)
+            }
+            e => e,
+        }
+    }
+}
+
+/// Parse `token` (a slice of `input`) reporting its location on failure
+pub fn parse_at<T>(input: &str, token: &str) -> Result<T, Error>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    token.parse::<T>().map_err(|e| {
+        ParseError::at(input, token, format!("couldn't parse '{token}' :: {e}")).into()
+    })
+}
+
+impl From<Diagnostic> for Error {
+    fn from(d: Diagnostic) -> Self {
+        Error::Config {
+            file: d.file,
+            line: Some(d.line),
+            column: Some(d.column),
+            reason: d.reason,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io { path, error } => write!(f, "<{}> :: {}", path.display(), error),
+            Error::Unreadable { path, reason } => write!(f, "<{}> :: {reason}", path.display()),
+            Error::Http { url, reason } => write!(f, "<{url}> :: {reason}"),
+            Error::NotFound { name, tried } => {
+                write!(f, "<{name}> not found, tried")?;
+                for path in tried {
+                    write!(f, "\n    {}", path.display())?;
+                }
+                Ok(())
+            }
+            Error::Config {
+                file,
+                line: Some(line),
+                column: Some(column),
+                reason,
+            } => write!(
+                f,
+                "format error in <{file}> line {line} column {column} :: {reason}"
+            ),
+            Error::Config {
+                file,
+                line: Some(line),
+                reason,
+                ..
+            } => write!(f, "format error in <{file}> line {line} :: {reason}"),
+            Error::Config {
+                file,
+                line: None,
+                reason,
+                ..
+            } => write!(f, "format error in <{file}> :: {reason}"),
+            Error::Parse(e) => e.fmt(f),
+            Error::Solver(reason) => f.write_str(reason),
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<{}:{}:{}> :: {}",
+            self.file, self.line, self.column, self.reason
+        )
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let file = self.file.as_deref().unwrap_or("input");
+        writeln!(
+            f,
+            "<{}:{}:{}> :: {}",
+            file, self.line, self.column, self.reason
+        )?;
+        writeln!(f, "    {}", self.line_text)?;
+        write!(f, "    {:>width$}", "^", width = self.column)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn locates_fragment() {
+        let input = "#.#\n#x#\n";
+        let e = ParseError::at(input, &input[5..6], "bad");
+        assert_eq!((2, 2), (e.line, e.column));
+        assert_eq!("x", e.text);
+        assert_eq!("#x#", e.line_text);
+    }
+
+    #[test]
+    fn foreign_fragment_is_reported_at_the_end() {
+        let input = "12\n34";
+        let e = ParseError::at(input, "elsewhere", "bad");
+        assert_eq!((2, 3), (e.line, e.column));
+    }
+}
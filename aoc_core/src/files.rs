@@ -0,0 +1,646 @@
+use crate::crypt::{self, Key};
+use crate::error::Error;
+use crate::misc::AResult;
+use std::io::{BufRead, Read};
+use std::path::{Path, PathBuf};
+
+pub type FileReader = std::io::BufReader<std::fs::File>;
+
+/// Where a case gets its puzzle input from
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    /// a file found on the input search paths, see files::search_paths, or `-` for stdin
+    File(String),
+    /// the text itself, written out in the config
+    Inline(String),
+}
+
+impl std::fmt::Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Input::File(filename) => f.write_str(filename),
+            Input::Inline(_) => f.write_str("inline input"),
+        }
+    }
+}
+
+/// The input's text, normalised as Normalise::default describes
+pub fn load_input(input: &Input) -> AResult<String> {
+    load_input_with(input, Normalise::default())
+}
+
+pub fn load_input_with(input: &Input, options: Normalise) -> AResult<String> {
+    let text = match input {
+        Input::File(filename) => load_full_input_as_string(filename)?,
+        Input::Inline(text) => text.clone(),
+    };
+    Ok(normalise(&text, options))
+}
+
+/// How an input is tidied before a solver sees it, so one saved on any platform reads the same.
+/// Line endings always become `\n` and trailing newlines are dropped.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Normalise {
+    /// drop the whitespace at the end of every line too
+    pub trim_lines: bool,
+}
+
+pub fn normalise(text: &str, options: Normalise) -> String {
+    let mut normalised = String::with_capacity(text.len());
+    for line in text.lines() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        normalised += match options.trim_lines {
+            true => line.trim_end(),
+            false => line,
+        };
+        normalised.push('\n');
+    }
+    let end = normalised.trim_end_matches('\n').len();
+    normalised.truncate(end);
+    normalised
+}
+
+/// The blank line separated sections of the text, each a slice of it without its
+/// surrounding newlines, however many blank lines come between them
+pub fn split_sections(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let start = rest.trim_start_matches(['\n', '\r']);
+        if start.is_empty() {
+            return None;
+        }
+        let offset = text.len() - start.len();
+        let length = [start.find("\n\n"), start.find("\n\r\n")]
+            .into_iter()
+            .flatten()
+            .min()
+            .unwrap_or(start.len());
+        rest = &start[length..];
+        Some(text[offset..offset + length].trim_end_matches(['\n', '\r']))
+    })
+}
+
+/// The lines of the text, whichever line endings it has
+pub fn split_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
+}
+
+/// The text's whitespace separated words, however they are spaced or broken across lines
+pub fn split_words(text: &str) -> impl Iterator<Item = &str> {
+    text.split_whitespace()
+}
+
+/// Set to a directory, or a list of them separated like PATH, to look there for inputs first
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// An input named this is read from stdin
+pub const STDIN: &str = "-";
+
+/// stdin can only be read once, so every input naming it shares the one text
+static STDIN_TEXT: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Set once stdin has been handed out as a stream, which leaves nothing to read after it
+static STDIN_STREAMED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+fn stdin_streamed() -> Error {
+    Error::Unreadable {
+        path: PathBuf::from("<stdin>"),
+        reason: "was streamed already and can only be read once".to_string(),
+    }
+}
+
+pub fn load_full_input_as_string(filename: &str) -> AResult<String> {
+    load_input_in(filename, &search_paths())
+}
+
+/// As load_full_input_as_string, looking in `dirs` rather than the usual search paths
+pub fn load_input_in(filename: &str, dirs: &[PathBuf]) -> AResult<String> {
+    if filename == STDIN {
+        return read_stdin();
+    }
+
+    let path = find_input_in(filename, dirs)?;
+    let mut file = open_path(&path)?;
+    let mut buffer = Vec::with_capacity(4096);
+
+    let io_error = |error| Error::Io {
+        path: path.clone(),
+        error,
+    };
+
+    let byte_count = file.read_to_end(&mut buffer).map_err(io_error)?;
+
+    if byte_count == 0 {
+        return Err(io_error(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "no data found",
+        )));
+    }
+
+    decode(&path, buffer)
+}
+
+fn read_stdin() -> AResult<String> {
+    if let Some(text) = STDIN_TEXT.get() {
+        return Ok(text.clone());
+    }
+    if STDIN_STREAMED.load(std::sync::atomic::Ordering::SeqCst) {
+        return Err(stdin_streamed());
+    }
+
+    let path = PathBuf::from("<stdin>");
+    let mut buffer = Vec::new();
+    std::io::stdin()
+        .read_to_end(&mut buffer)
+        .map_err(|error| Error::Io {
+            path: path.clone(),
+            error,
+        })?;
+    let text = decode(&path, buffer)?;
+    Ok(STDIN_TEXT.get_or_init(|| text).clone())
+}
+
+/// The text of a plain or encrypted file, see crypt
+fn decode(path: &Path, mut data: Vec<u8>) -> AResult<String> {
+    if crypt::is_encrypted(&data) {
+        data = decrypt(path, data)?;
+    }
+    String::from_utf8(data).map_err(|_| Error::Unreadable {
+        path: path.to_path_buf(),
+        reason: "is not text".to_string(),
+    })
+}
+
+fn decrypt(path: &Path, data: Vec<u8>) -> AResult<Vec<u8>> {
+    let unreadable = |reason| Error::Unreadable {
+        path: path.to_path_buf(),
+        reason,
+    };
+    let key = Key::from_env().map_err(|e| unreadable(format!("is encrypted but {e}")))?;
+    crypt::decrypt(&key, &data).map_err(unreadable)
+}
+
+pub fn open_file(project_relative_filename: &str) -> AResult<FileReader> {
+    open_path(&find_input(project_relative_filename)?)
+}
+
+fn open_path(path: &Path) -> AResult<FileReader> {
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .open(path)
+        .map_err(|error| Error::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+    Ok(std::io::BufReader::new(file))
+}
+
+/// Reads the input as it comes rather than all at once, for inputs too big to hold.
+/// An encrypted input can only be checked whole so it is decrypted into memory first,
+/// and stdin is streamed unless it has already been read, which it can be only once.
+pub fn open_input(filename: &str) -> AResult<Box<dyn BufRead>> {
+    open_input_in(filename, &search_paths())
+}
+
+/// As open_input, looking in `dirs` rather than the usual search paths
+pub fn open_input_in(filename: &str, dirs: &[PathBuf]) -> AResult<Box<dyn BufRead>> {
+    if filename == STDIN {
+        return match STDIN_TEXT.get() {
+            Some(text) => Ok(Box::new(std::io::Cursor::new(text.clone().into_bytes()))),
+            None if STDIN_STREAMED.swap(true, std::sync::atomic::Ordering::SeqCst) => {
+                Err(stdin_streamed())
+            }
+            None => Ok(Box::new(std::io::stdin().lock())),
+        };
+    }
+
+    let path = find_input_in(filename, dirs)?;
+    let mut file = open_path(&path)?;
+    let start = file.fill_buf().map_err(|error| Error::Io {
+        path: path.clone(),
+        error,
+    })?;
+    if !crypt::is_encrypted(start) {
+        return Ok(Box::new(file));
+    }
+
+    let mut data = Vec::new();
+    file.read_to_end(&mut data).map_err(|error| Error::Io {
+        path: path.clone(),
+        error,
+    })?;
+    Ok(Box::new(std::io::Cursor::new(decrypt(&path, data)?)))
+}
+
+/// The input a line at a time, without its line ending
+pub fn input_lines(filename: &str) -> AResult<Lines> {
+    Ok(Lines::new(filename, open_input(filename)?))
+}
+
+pub struct Lines {
+    path: PathBuf,
+    reader: Box<dyn BufRead>,
+}
+
+impl Lines {
+    /// `name` says where the reader's text comes from in errors
+    pub fn new(name: &str, reader: Box<dyn BufRead>) -> Self {
+        Lines {
+            path: PathBuf::from(name),
+            reader,
+        }
+    }
+}
+
+impl Iterator for Lines {
+    type Item = AResult<String>;
+
+    fn next(&mut self) -> Option<AResult<String>> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => None,
+            Ok(_) => {
+                let end = line.trim_end_matches(['\n', '\r']).len();
+                line.truncate(end);
+                Some(Ok(line))
+            }
+            Err(error) => Some(Err(match error.kind() {
+                std::io::ErrorKind::InvalidData => Error::Unreadable {
+                    path: self.path.clone(),
+                    reason: "is not text".to_string(),
+                },
+                _ => Error::Io {
+                    path: self.path.clone(),
+                    error,
+                },
+            })),
+        }
+    }
+}
+
+/// The input in pieces of at most `size` bytes, the one buffer being reused for each
+pub fn input_chunks(filename: &str, size: usize) -> AResult<Chunks> {
+    Ok(Chunks::new(filename, open_input(filename)?, size))
+}
+
+pub struct Chunks {
+    path: PathBuf,
+    reader: Box<dyn BufRead>,
+    buffer: Vec<u8>,
+}
+
+impl Chunks {
+    /// `name` says where the reader's bytes come from in errors
+    pub fn new(name: &str, reader: Box<dyn BufRead>, size: usize) -> Self {
+        Chunks {
+            path: PathBuf::from(name),
+            reader,
+            buffer: vec![0; size.max(1)],
+        }
+    }
+
+    /// The next piece, or None once the input is used up
+    pub fn next_chunk(&mut self) -> AResult<Option<&[u8]>> {
+        let count = loop {
+            match self.reader.read(&mut self.buffer) {
+                Ok(count) => break count,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    return Err(Error::Io {
+                        path: self.path.clone(),
+                        error,
+                    })
+                }
+            }
+        };
+        Ok((count > 0).then(|| &self.buffer[..count]))
+    }
+}
+
+/// The input's bytes mapped into memory, so only the pages being looked at are loaded.
+/// Encrypted inputs, stdin, and platforms without mmap get a copy in memory instead.
+pub fn map_input(filename: &str) -> AResult<Mapped> {
+    if filename == STDIN {
+        return Ok(Mapped {
+            path: PathBuf::from("<stdin>"),
+            bytes: Bytes::Owned(read_stdin()?.into_bytes()),
+        });
+    }
+
+    let path = find_input(filename)?;
+    let file = std::fs::File::open(&path).map_err(|error| Error::Io {
+        path: path.clone(),
+        error,
+    })?;
+    let bytes = Bytes::map(&file).map_err(|error| Error::Io {
+        path: path.clone(),
+        error,
+    })?;
+
+    let bytes = match crypt::is_encrypted(bytes.as_slice()) {
+        true => Bytes::Owned(decrypt(&path, bytes.as_slice().to_vec())?),
+        false => bytes,
+    };
+    Ok(Mapped { path, bytes })
+}
+
+pub struct Mapped {
+    path: PathBuf,
+    bytes: Bytes,
+}
+
+impl Mapped {
+    /// The bytes as text, checked without being copied
+    pub fn text(&self) -> AResult<&str> {
+        std::str::from_utf8(self).map_err(|_| Error::Unreadable {
+            path: self.path.clone(),
+            reason: "is not text".to_string(),
+        })
+    }
+}
+
+impl std::ops::Deref for Mapped {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+}
+
+enum Bytes {
+    #[cfg(unix)]
+    Map {
+        address: std::ptr::NonNull<libc::c_void>,
+        length: usize,
+    },
+    Owned(Vec<u8>),
+}
+
+// the mapping is read only and private to the process, like an immutable Vec<u8>
+unsafe impl Send for Bytes {}
+unsafe impl Sync for Bytes {}
+
+impl Bytes {
+    #[cfg(unix)]
+    fn map(file: &std::fs::File) -> std::io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let length = usize::try_from(file.metadata()?.len())
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::OutOfMemory, "too big to map"))?;
+        // mmap refuses an empty mapping
+        if length == 0 {
+            return Ok(Bytes::Owned(Vec::new()));
+        }
+
+        // SAFETY: a fresh read only mapping of the whole file, checked before use.
+        // Inputs aren't expected to change while a solver reads them.
+        let address = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                length,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if address == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Bytes::Map {
+            address: std::ptr::NonNull::new(address).unwrap(),
+            length,
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn map(mut file: &std::fs::File) -> std::io::Result<Self> {
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        Ok(Bytes::Owned(data))
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            // SAFETY: the mapping lives until drop and is `length` bytes long
+            #[cfg(unix)]
+            Bytes::Map { address, length } => unsafe {
+                std::slice::from_raw_parts(address.as_ptr() as *const u8, *length)
+            },
+            Bytes::Owned(data) => data,
+        }
+    }
+}
+
+impl Drop for Bytes {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Bytes::Map { address, length } = self {
+            // SAFETY: unmapping what map mapped, nothing borrows it any more
+            unsafe {
+                libc::munmap(address.as_ptr(), *length);
+            }
+        }
+    }
+}
+
+/// Where inputs are looked for, in order: the directories in AOC_INPUT_DIR,
+/// <input> in the current directory, then the <input> folder in the root of the workspace
+pub fn search_paths() -> Vec<PathBuf> {
+    let mut dirs = std::env::var_os(INPUT_DIR_VAR)
+        .map(|dirs| std::env::split_paths(&dirs).collect::<Vec<_>>())
+        .unwrap_or_default();
+    dirs.push(PathBuf::from("input"));
+    dirs.push(workspace_input_dir());
+    dirs
+}
+
+fn workspace_input_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("input")
+}
+
+pub fn find_input(filename: &str) -> AResult<PathBuf> {
+    find_input_in(filename, &search_paths())
+}
+
+/// The first of `dirs` holding the file, or an encrypted copy of it,
+/// an absolute filename is only looked for where it says
+pub fn find_input_in(filename: &str, dirs: &[PathBuf]) -> AResult<PathBuf> {
+    let encrypted = format!("{filename}{}", crypt::EXTENSION);
+    let tried = match Path::new(filename).is_absolute() {
+        true => vec![PathBuf::from(filename), PathBuf::from(&encrypted)],
+        false => dirs
+            .iter()
+            .flat_map(|dir| [dir.join(filename), dir.join(&encrypted)])
+            .collect(),
+    };
+
+    match tried.iter().find(|path| path.is_file()) {
+        Some(path) => Ok(path.clone()),
+        None => Err(Error::NotFound {
+            name: filename.to_string(),
+            tried,
+        }),
+    }
+}
+
+/// Whether the input can be read, stdin always counting as there
+pub fn input_exists(filename: &str) -> bool {
+    filename == STDIN || find_input(filename).is_ok()
+}
+
+/// Writes an input or config, encrypted again if that is how it was found,
+/// otherwise where input_path says, returning where it went
+pub fn write_input(filename: &str, text: &str) -> AResult<PathBuf> {
+    let path = input_path(filename);
+    let io_error = |error| Error::Io {
+        path: path.clone(),
+        error,
+    };
+
+    let data = match path.to_string_lossy().ends_with(crypt::EXTENSION) {
+        true => {
+            let key = Key::from_env().map_err(|e| Error::Unreadable {
+                path: path.clone(),
+                reason: format!("is encrypted but {e}"),
+            })?;
+            crypt::encrypt(&key, text.as_bytes())
+        }
+        false => text.as_bytes().to_vec(),
+    };
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    std::fs::write(&path, data).map_err(io_error)?;
+    Ok(path)
+}
+
+/// Where the input is, or where it would be written if it doesn't exist yet
+pub fn input_path(filename: &str) -> PathBuf {
+    find_input(filename).unwrap_or_else(|_| {
+        let first = search_paths().into_iter().next().unwrap();
+        first.join(filename)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lists_every_path_tried() {
+        let dirs = [PathBuf::from("first"), PathBuf::from("second")];
+        let error = find_input_in("missing.txt", &dirs).unwrap_err();
+        assert_eq!(
+            format!(
+                "<missing.txt> not found, tried\n    {}\n    {}\n    {}\n    {}",
+                Path::new("first").join("missing.txt").display(),
+                Path::new("first").join("missing.txt.enc").display(),
+                Path::new("second").join("missing.txt").display(),
+                Path::new("second").join("missing.txt.enc").display()
+            ),
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn takes_the_first_dir_holding_the_input() {
+        let dirs = [
+            PathBuf::from("no such dir"),
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+        ];
+        assert_eq!(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"),
+            find_input_in("Cargo.toml", &dirs).unwrap()
+        );
+        assert!(find_input_in("src", &dirs).is_err());
+    }
+
+    #[test]
+    fn reads_plain_and_encrypted_text() {
+        let path = Path::new("day1.txt");
+        assert_eq!("17\n", decode(path, b"17\n".to_vec()).unwrap());
+        assert_eq!(
+            "<day1.txt> :: is not text",
+            decode(path, vec![0xff]).unwrap_err().to_string()
+        );
+
+        // with no key in the environment
+        if Key::from_env().is_err() {
+            let sealed = crypt::encrypt(&Key::generate(), b"17\n");
+            assert!(decode(path, sealed)
+                .unwrap_err()
+                .to_string()
+                .starts_with("<day1.txt> :: is encrypted but no key"));
+        }
+    }
+
+    #[test]
+    fn streams_maps_and_chunks_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc-files-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("big.txt");
+        std::fs::write(&path, "190: 10 19\r\n3267: 81 40 27\nlast").unwrap();
+        let name = path.to_str().unwrap();
+
+        let lines = input_lines(name).unwrap().collect::<AResult<Vec<_>>>();
+        assert_eq!(vec!["190: 10 19", "3267: 81 40 27", "last"], lines.unwrap());
+
+        let mut chunks = input_chunks(name, 8).unwrap();
+        let mut joined = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().unwrap() {
+            assert!(chunk.len() <= 8);
+            joined.extend_from_slice(chunk);
+        }
+        assert_eq!(std::fs::read(&path).unwrap(), joined);
+
+        let mapped = map_input(name).unwrap();
+        assert_eq!(
+            load_full_input_as_string(name).unwrap(),
+            mapped.text().unwrap()
+        );
+
+        let empty = dir.join("empty.txt");
+        std::fs::write(&empty, "").unwrap();
+        assert!(map_input(empty.to_str().unwrap()).unwrap().is_empty());
+        assert_eq!(0, input_lines(empty.to_str().unwrap()).unwrap().count());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn normalises_line_endings() {
+        let text = "125 17 \r\n\r\n8\r\n\r\n";
+        assert_eq!("125 17 \n\n8", normalise(text, Normalise::default()));
+        assert_eq!(
+            "125 17\n\n8",
+            normalise(text, Normalise { trim_lines: true })
+        );
+        assert_eq!("", normalise("\n", Normalise::default()));
+    }
+
+    #[test]
+    fn splits_sections_lines_and_words() {
+        for text in [
+            "47|53\n97|13\n\n75,47\n",
+            "47|53\r\n97|13\r\n\r\n\r\n75,47\r\n",
+        ] {
+            let sections = split_sections(text).collect::<Vec<_>>();
+            assert_eq!(2, sections.len());
+            assert_eq!(
+                vec!["47|53", "97|13"],
+                split_lines(sections[0]).collect::<Vec<_>>()
+            );
+            assert_eq!("75,47", sections[1]);
+        }
+        assert_eq!(0, split_sections("\n\n").count());
+        assert_eq!(
+            vec!["125", "17"],
+            split_words(" 125  17\n").collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! A stand-in for libtest, used by each day's `tests/cases.rs` (harness = false).
+//! The config is read when the tests run, so editing it needs no rebuild,
+//! and every case becomes a test named `<day>::part<N>::<mode>::<case>`.
+//! Inputs are not committed, so a case whose input file is missing is ignored rather than failed,
+//! and `--ignored` picks out just those cases.
+
+use crate::config::{read_config, Mode, Part};
+use crate::files::{input_exists, Input};
+use crate::runner;
+use crate::solution::Solution;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::process::ExitCode;
+use std::time::Instant;
+
+/// What to run, from the arguments `cargo test` passes through
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    filters: Vec<String>,
+    skip: Vec<String>,
+    exact: bool,
+    list: bool,
+    /// only the cases that would be ignored, to see which inputs are missing
+    ignored: bool,
+    day: Option<String>,
+    part: Option<u8>,
+    mode: Option<Mode>,
+    /// options for libtest or a newer harness, warned about and otherwise left alone
+    unknown: Vec<String>,
+}
+
+impl Options {
+    /// Takes `--flag value` and `--flag=value` alike. Options this harness doesn't know,
+    /// such as other libtest flags, are kept to be warned about rather than failing the run.
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("missing value for {flag}"))
+            };
+            match flag {
+                "--exact" => options.exact = true,
+                "--list" => options.list = true,
+                "--skip" => options.skip.push(value()?),
+                "--day" => options.day = Some(value()?),
+                "--part" => {
+                    let part = value()?;
+                    options.part = Some(part.parse().map_err(|_| format!("unknown part {part}"))?)
+                }
+                "--mode" => options.mode = Some(value()?.parse()?),
+                // libtest options that make no difference here
+                "--nocapture" | "--show-output" | "-q" | "--quiet" => {}
+                "--ignored" => options.ignored = true,
+                // cases are only ignored when they can't be run, so they are always included
+                "--include-ignored" => options.ignored = false,
+                "--test-threads" | "--format" | "--color" | "--logfile" | "--shuffle-seed"
+                | "-Z" => _ = value()?,
+                _ if flag.starts_with('-') => options.unknown.push(arg.clone()),
+                _ => options.filters.push(arg.clone()),
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn selects(&self, day: &str, name: &str, part: &Part) -> bool {
+        let matches = |filter: &String| match self.exact {
+            true => name == filter,
+            false => name.contains(filter.as_str()),
+        };
+
+        self.day.as_ref().is_none_or(|d| d == day)
+            && self.part.is_none_or(|p| p == part.part)
+            && self.mode.is_none_or(|m| m == part.mode)
+            && (self.filters.is_empty() || self.filters.iter().any(matches))
+            && !self.skip.iter().any(|s| name.contains(s.as_str()))
+            && (!self.ignored || missing_input(part).is_some())
+    }
+}
+
+fn test_name(day: &str, part: &Part) -> String {
+    format!(
+        "{day}::part{}::{}::{}",
+        part.part,
+        part.mode.to_string().to_lowercase(),
+        part.name
+    )
+}
+
+/// Why a case can't be run on this machine
+fn missing_input(part: &Part) -> Option<String> {
+    match &part.input {
+        Input::File(filename) if !input_exists(filename) => {
+            Some(format!("input <{filename}> not found"))
+        }
+        _ => None,
+    }
+}
+
+/// Run every case in the day's config, reporting in the same format as `cargo test`
+pub fn run<S: Solution>(day: &str) -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+    for option in &options.unknown {
+        eprintln!("warning: ignoring unknown option {option}");
+    }
+
+    let parts = match read_config(day) {
+        Ok(parts) => parts,
+        Err(e) => {
+            eprintln!("{day} :: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let cases = parts
+        .iter()
+        .map(|p| (test_name(day, p), p))
+        .collect::<Vec<_>>();
+    let selected = cases
+        .iter()
+        .filter(|(name, part)| options.selects(day, name, part))
+        .collect::<Vec<_>>();
+
+    if options.list {
+        for (name, _) in &selected {
+            println!("{name}: test");
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let now = Instant::now();
+    println!("\nrunning {} tests", selected.len());
+
+    let mut failures = Vec::new();
+    let mut ignored = 0;
+    for (name, part) in &selected {
+        if let Some(reason) = missing_input(part) {
+            println!("test {name} ... ignored, {reason}");
+            ignored += 1;
+            continue;
+        }
+        match run_case::<S>(part) {
+            Ok(()) => println!("test {name} ... ok"),
+            Err(reason) => {
+                println!("test {name} ... FAILED");
+                failures.push((name, reason));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:\n");
+        for (name, reason) in &failures {
+            println!("---- {name} ----\n{reason}\n");
+        }
+    }
+
+    println!(
+        "\ntest result: {}. {} passed; {} failed; {} ignored; 0 measured; {} filtered out; finished in {:.2}s\n",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        selected.len() - failures.len() - ignored,
+        failures.len(),
+        ignored,
+        cases.len() - selected.len(),
+        now.elapsed().as_secs_f64()
+    );
+
+    match failures.is_empty() {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+fn run_case<S: Solution>(part: &Part) -> Result<(), String> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        runner::run::<S>(&part.input, part.part)
+    }));
+
+    match result {
+        Ok(Ok(actual)) if actual == part.expected => Ok(()),
+        Ok(Ok(actual)) => Err(format!("expected {} but got {actual}", part.expected)),
+        Ok(Err(e)) => Err(format!("<{}> :: {e}", part.input)),
+        Err(_) => Err("the solver panicked".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::files::Input;
+
+    fn options(args: &str) -> Result<Options, String> {
+        Options::parse(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn reads_cargo_test_arguments() {
+        assert_eq!(
+            Ok(Options {
+                filters: vec!["example".to_string()],
+                exact: true,
+                part: Some(2),
+                mode: Some(Mode::Real),
+                ..Options::default()
+            }),
+            options("--test-threads 1 --exact example -q --part 2 --mode real")
+        );
+        assert_eq!(
+            options("--test-threads 1 --exact example -q --part 2 --mode real"),
+            options("--test-threads=1 --exact example -q --part=2 --mode=real")
+        );
+        assert_eq!(
+            Ok(vec!["--bogus".to_string(), "--report-time".to_string()]),
+            options("--bogus -Z unstable-options --report-time").map(|o| o.unknown)
+        );
+        assert_eq!(
+            Ok(Options::default()),
+            options("--ignored --include-ignored")
+        );
+        assert!(options("--part").is_err());
+        assert!(options("--part=").is_err());
+    }
+
+    #[test]
+    fn ignores_cases_without_their_input() {
+        let mut part = Part {
+            name: "real".to_string(),
+            part: 1,
+            mode: Mode::Real,
+            input: Input::File("no such input.txt".to_string()),
+            expected: 1.into(),
+            tags: Vec::new(),
+            notes: None,
+        };
+        assert_eq!(
+            Some("input <no such input.txt> not found".to_string()),
+            missing_input(&part)
+        );
+
+        part.input = Input::Inline("1".to_string());
+        assert_eq!(None, missing_input(&part));
+    }
+
+    #[test]
+    fn filters_by_day_part_mode_and_name() {
+        let part = Part {
+            name: "example".to_string(),
+            part: 1,
+            mode: Mode::Test,
+            input: Input::Inline("1".to_string()),
+            expected: 1.into(),
+            tags: Vec::new(),
+            notes: None,
+        };
+        let name = test_name("day1", &part);
+        assert_eq!("day1::part1::test::example", name);
+
+        let selects = |args: &str| options(args).unwrap().selects("day1", &name, &part);
+        assert!(selects(""));
+        assert!(selects("--day day1 --part 1 --mode test"));
+        assert!(selects("part1 nothing"));
+        assert!(!selects("--day day2"));
+        assert!(!selects("--part 2"));
+        assert!(!selects("--mode real"));
+        assert!(!selects("--exact example"));
+        assert!(!selects("--skip example"));
+        assert!(!selects("--ignored"));
+        assert!(selects("--include-ignored"));
+
+        let missing = Part {
+            input: Input::File("no such input.txt".to_string()),
+            ..part
+        };
+        assert!(options("--ignored")
+            .unwrap()
+            .selects("day1", &name, &missing));
+    }
+}
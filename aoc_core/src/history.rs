@@ -0,0 +1,294 @@
+//! Every answer submitted and what the server said of it, kept in <answers.toml> beside
+//! the inputs, so a value already known to be wrong is caught before it is sent again.
+//! Each submission is one [[answer]] table, shared by every version of a day, e.g.
+//!
+//! [[answer]]
+//! day = 16
+//! part = 1
+//! answer = 7040
+//! verdict = "too high"
+//! time = 1734307200           # seconds since the unix epoch
+
+use crate::answer::Answer;
+use crate::client::Verdict;
+use crate::config::{Mode, Part};
+use crate::error::Error;
+use crate::files::{input_exists, load_full_input_as_string, write_input};
+use crate::misc::{puzzle_day, AResult};
+use crate::toml::{self, quote, Fields, Value};
+use std::path::PathBuf;
+
+pub const HISTORY_FILE: &str = "answers.toml";
+
+/// One answer given for a part, with a verdict that says something about it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submission {
+    pub day: u32,
+    pub part: u8,
+    pub answer: Answer,
+    pub verdict: Verdict,
+    pub time: u64,
+}
+
+/// Why an answer shouldn't be trusted, or sent
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    /// this very answer was already given and turned down
+    KnownWrong(Verdict),
+    /// at or above an answer that was too high
+    AboveBound(Answer),
+    /// at or below an answer that was too low
+    BelowBound(Answer),
+    /// the part has been solved, with a different answer
+    NotTheRightAnswer(Answer),
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::KnownWrong(verdict) => write!(f, "already submitted, it was {verdict}"),
+            Warning::AboveBound(bound) => write!(f, "{bound} was already too high"),
+            Warning::BelowBound(bound) => write!(f, "{bound} was already too low"),
+            Warning::NotTheRightAnswer(right) => write!(f, "the right answer was {right}"),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct History {
+    pub submissions: Vec<Submission>,
+}
+
+impl History {
+    /// The recorded submissions, or none if nothing has been submitted yet
+    pub fn load() -> AResult<Self> {
+        if !input_exists(HISTORY_FILE) {
+            return Ok(History::default());
+        }
+        Self::read(HISTORY_FILE, &load_full_input_as_string(HISTORY_FILE)?)
+    }
+
+    pub fn read(file: &str, text: &str) -> AResult<Self> {
+        let (tables, diagnostics) = toml::parse(file, text);
+        if let Some(first) = diagnostics.into_iter().next() {
+            return Err(first.into());
+        }
+
+        let mut history = History::default();
+        for table in tables.iter().filter(|t| t.name == "answer") {
+            let fields = Fields { file, table };
+            let answer = match fields.value("answer")? {
+                Value::Integer(raw) => raw.parse().unwrap(),
+                Value::String(text) => Answer::Text(text.clone()),
+                other => return Err(fields.wrong_kind("answer", "a number or a string", other)),
+            };
+            let verdict = fields.string("verdict")?;
+
+            history.submissions.push(Submission {
+                day: fields.number("day")?,
+                part: fields.number("part")?,
+                answer,
+                verdict: verdict_from_name(&verdict).ok_or_else(|| {
+                    Error::config(
+                        file,
+                        Some(table.line),
+                        format!("unknown verdict '{verdict}'"),
+                    )
+                })?,
+                time: fields.number("time")?,
+            });
+        }
+        Ok(history)
+    }
+
+    /// Adds the submission to the history file, if its verdict tells us anything
+    pub fn save(
+        &mut self,
+        day: u32,
+        part: u8,
+        answer: &Answer,
+        verdict: &Verdict,
+    ) -> AResult<Option<PathBuf>> {
+        let existing = match input_exists(HISTORY_FILE) {
+            true => load_full_input_as_string(HISTORY_FILE)?,
+            false => String::new(),
+        };
+        match self.record(day, part, answer, verdict) {
+            Some(text) => write_input(HISTORY_FILE, &(existing + &text)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Adds the submission, returning the text to append to the file,
+    /// or nothing for a verdict that says nothing about the answer
+    pub fn record(
+        &mut self,
+        day: u32,
+        part: u8,
+        answer: &Answer,
+        verdict: &Verdict,
+    ) -> Option<String> {
+        let name = verdict_name(verdict)?;
+        let submission = Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict: verdict.clone(),
+            time: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        };
+        let literal = match answer {
+            Answer::Text(text) => quote(text),
+            number => number.to_string(),
+        };
+
+        let text = format!(
+            "\n[[answer]]\nday = {day}\npart = {part}\nanswer = {literal}\nverdict = {}\ntime = {}\n",
+            quote(name),
+            submission.time
+        );
+        self.submissions.push(submission);
+        Some(text)
+    }
+
+    /// What is known against the answer, from the earlier submissions for its part
+    pub fn check(&self, day: u32, part: u8, answer: &Answer) -> Option<Warning> {
+        let earlier = self
+            .submissions
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect::<Vec<_>>();
+
+        if let Some(right) = earlier.iter().find(|s| s.verdict == Verdict::Right) {
+            return match right.answer == *answer {
+                true => None,
+                false => Some(Warning::NotTheRightAnswer(right.answer.clone())),
+            };
+        }
+        if let Some(wrong) = earlier.iter().find(|s| s.answer == *answer) {
+            return Some(Warning::KnownWrong(wrong.verdict.clone()));
+        }
+
+        let value = number(answer)?;
+        let bound = |verdict: Verdict| {
+            earlier
+                .iter()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| Some((number(&s.answer)?, &s.answer)))
+        };
+        if let Some((_, high)) = bound(Verdict::TooHigh)
+            .filter(|(h, _)| value >= *h)
+            .min_by_key(|(h, _)| *h)
+        {
+            return Some(Warning::AboveBound(high.clone()));
+        }
+        if let Some((_, low)) = bound(Verdict::TooLow)
+            .filter(|(l, _)| value <= *l)
+            .max_by_key(|(l, _)| *l)
+        {
+            return Some(Warning::BelowBound(low.clone()));
+        }
+        None
+    }
+
+    /// As check, for the answer a case of the named day gave, examples having no history
+    pub fn check_case(&self, day: &str, case: &Part, answer: &Answer) -> Option<Warning> {
+        match (case.mode, puzzle_day(day)) {
+            (Mode::Real, Some(day)) => self.check(day, case.part, answer),
+            _ => None,
+        }
+    }
+}
+
+fn number(answer: &Answer) -> Option<i128> {
+    match answer {
+        Answer::Signed(v) => Some(*v as i128),
+        Answer::Unsigned(v) => i128::try_from(*v).ok(),
+        Answer::Text(_) => None,
+    }
+}
+
+fn verdict_name(verdict: &Verdict) -> Option<&'static str> {
+    match verdict {
+        Verdict::Right => Some("right"),
+        Verdict::Wrong => Some("wrong"),
+        Verdict::TooHigh => Some("too high"),
+        Verdict::TooLow => Some("too low"),
+        _ => None,
+    }
+}
+
+fn verdict_from_name(name: &str) -> Option<Verdict> {
+    [
+        Verdict::Right,
+        Verdict::Wrong,
+        Verdict::TooHigh,
+        Verdict::TooLow,
+    ]
+    .into_iter()
+    .find(|verdict| verdict_name(verdict) == Some(name))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn history(submissions: &[(u8, Answer, Verdict)]) -> History {
+        let mut history = History::default();
+        for (part, answer, verdict) in submissions {
+            history.record(16, *part, answer, verdict);
+        }
+        history
+    }
+
+    #[test]
+    fn reads_back_what_it_records() {
+        let mut recorded = History::default();
+        let mut text = String::new();
+        text += &recorded
+            .record(16, 1, &Answer::Signed(7040), &Verdict::TooHigh)
+            .unwrap();
+        text += &recorded
+            .record(16, 2, &"6,1".into(), &Verdict::Wrong)
+            .unwrap();
+        assert_eq!(
+            None,
+            recorded.record(16, 2, &"6,2".into(), &Verdict::RateLimited(None))
+        );
+
+        assert_eq!(recorded, History::read(HISTORY_FILE, &text).unwrap());
+    }
+
+    #[test]
+    fn warns_outside_the_known_bounds() {
+        let history = history(&[
+            (1, Answer::Signed(500), Verdict::TooHigh),
+            (1, Answer::Signed(400), Verdict::TooHigh),
+            (1, Answer::Signed(100), Verdict::TooLow),
+            (1, Answer::Signed(250), Verdict::Wrong),
+        ]);
+        let check = |answer: i64| history.check(16, 1, &Answer::Signed(answer));
+
+        assert_eq!(Some(Warning::AboveBound(Answer::Signed(400))), check(450));
+        assert_eq!(Some(Warning::KnownWrong(Verdict::TooHigh)), check(400));
+        assert_eq!(Some(Warning::BelowBound(Answer::Signed(100))), check(-3));
+        assert_eq!(Some(Warning::KnownWrong(Verdict::Wrong)), check(250));
+        assert_eq!(None, check(300));
+        assert_eq!(None, history.check(16, 2, &Answer::Signed(450)));
+    }
+
+    #[test]
+    fn warns_once_solved() {
+        let history = history(&[
+            (2, Answer::Signed(10), Verdict::TooLow),
+            (2, Answer::Signed(45), Verdict::Right),
+        ]);
+
+        assert_eq!(None, history.check(16, 2, &Answer::Unsigned(45)));
+        assert_eq!(
+            Some(Warning::NotTheRightAnswer(Answer::Signed(45))),
+            history.check(16, 2, &Answer::Signed(44))
+        );
+    }
+}
@@ -0,0 +1,19 @@
+// shared by every day: input loading, config parsing, the Part/Mode/Answer types
+// and the Solution trait each day implements
+pub mod answer;
+pub mod baseline;
+pub mod benchmarks;
+pub mod client;
+pub mod config;
+pub mod crypt;
+pub mod error;
+pub mod files;
+pub mod harness;
+pub mod history;
+pub mod misc;
+pub mod record;
+pub mod runner;
+pub mod solution;
+pub mod svg;
+pub mod toml;
+pub mod visual;
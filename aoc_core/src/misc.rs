@@ -0,0 +1,15 @@
+pub type AResult<T> = Result<T, crate::error::Error>;
+
+/// The puzzle a day crate solves, `day7_v2` being another go at day 7
+pub fn puzzle_day(day: &str) -> Option<u32> {
+    let digits = day.strip_prefix("day")?;
+    let end = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    digits[..end].parse().ok()
+}
+
+/// The file a day's real input is saved as, shared by every version of the day
+pub fn puzzle_input_name(day: u32) -> String {
+    format!("day{day}.txt")
+}
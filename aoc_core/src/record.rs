@@ -0,0 +1,379 @@
+//! Recordings of a solver's frames, to share or to look through a long run afterwards:
+//! an animated GIF with a block of colour for each cell, or an asciinema `.cast` of the
+//! coloured text. Both are visualisers, put in place by the runner like any other.
+
+use crate::error::Error;
+use crate::misc::AResult;
+use crate::visual::{Cell, Frame, Style, Visualiser};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Frames a second a recording plays at unless told otherwise
+pub const DEFAULT_FPS: u32 = 10;
+
+/// Pixels along each side of a cell's block in a GIF
+const BLOCK: usize = 4;
+
+/// The GIF palette, indexed by `colour`
+const PALETTE: [[u8; 3]; 8] = [
+    [0x10, 0x10, 0x14], // background
+    [0xd0, 0xd0, 0xd0], // anything else plain
+    [0x40, 0x40, 0x48], // faint
+    [0x70, 0x70, 0x78], // wall
+    [0x3c, 0xb0, 0x43], // path
+    [0x2a, 0xb7, 0xca], // side path
+    [0xff, 0xdd, 0x33], // marker
+    [0xe0, 0x40, 0xfb], // highlight
+];
+
+/// How fast a recording plays and how much of the run it keeps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pace {
+    pub fps: u32,
+    /// one frame kept in every this many, the last frame always being kept
+    pub every: usize,
+}
+
+impl Default for Pace {
+    fn default() -> Self {
+        Pace {
+            fps: DEFAULT_FPS,
+            every: 1,
+        }
+    }
+}
+
+/// Keeps the frames the pace asks for, holding on to the latest one skipped so a
+/// recording can end on the frame the run ended on
+struct Skipper {
+    every: usize,
+    seen: usize,
+    skipped: Option<Frame>,
+}
+
+impl Skipper {
+    fn new(pace: Pace) -> Self {
+        Self {
+            every: pace.every.max(1),
+            seen: 0,
+            skipped: None,
+        }
+    }
+
+    fn keeps(&mut self, frame: &Frame) -> bool {
+        let keep = self.seen.is_multiple_of(self.every);
+        self.seen += 1;
+        match (keep, &mut self.skipped) {
+            (true, skipped) => *skipped = None,
+            // reusing the last copy's rows, as a long run skips a lot of frames
+            (false, Some(skipped)) => skipped.clone_from(frame),
+            (false, skipped) => *skipped = Some(frame.clone()),
+        }
+        keep
+    }
+
+    fn last(&mut self) -> Option<Frame> {
+        self.skipped.take()
+    }
+}
+
+fn create(path: &Path) -> AResult<BufWriter<File>> {
+    let io = |error| Error::Io {
+        path: path.to_path_buf(),
+        error,
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(io)?;
+    }
+    File::create(path).map(BufWriter::new).map_err(io)
+}
+
+/// An animated GIF, each cell a block of the colour its style and symbol call for.
+/// Captions and events have nowhere to go in one and are left out.
+pub struct GifRecorder {
+    path: PathBuf,
+    skipper: Skipper,
+    /// hundredths of a second each frame is shown for
+    delay: u16,
+    /// started with the first frame, which sets the size of the picture
+    encoder: Option<(gif::Encoder<BufWriter<File>>, usize, usize)>,
+}
+
+impl GifRecorder {
+    pub fn new(path: impl Into<PathBuf>, pace: Pace) -> Self {
+        Self {
+            path: path.into(),
+            skipper: Skipper::new(pace),
+            delay: (100 / pace.fps.max(1)).max(1) as u16,
+            encoder: None,
+        }
+    }
+
+    fn record(&mut self, frame: &Frame) -> AResult<()> {
+        if self.encoder.is_none() {
+            let cols = frame.rows().iter().map(Vec::len).max().unwrap_or(0).max(1);
+            let rows = frame.rows().len().max(1);
+            let (width, height) = (self.dimension(cols)?, self.dimension(rows)?);
+            let palette = PALETTE.concat();
+            let mut encoder = gif::Encoder::new(create(&self.path)?, width, height, &palette)
+                .map_err(|e| self.failed(e))?;
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(|e| self.failed(e))?;
+            self.encoder = Some((encoder, cols, rows));
+        }
+        let Some((encoder, cols, rows)) = &mut self.encoder else {
+            unreachable!("started above");
+        };
+
+        // frames larger than the first are cut down to its size
+        let mut pixels = vec![0; *cols * *rows * BLOCK * BLOCK];
+        for (row, cells) in frame.rows().iter().take(*rows).enumerate() {
+            for (col, cell) in cells.iter().take(*cols).enumerate() {
+                for y in 0..BLOCK {
+                    let start = ((row * BLOCK + y) * *cols + col) * BLOCK;
+                    pixels[start..start + BLOCK].fill(colour(cell));
+                }
+            }
+        }
+
+        let frame = gif::Frame {
+            width: (*cols * BLOCK) as u16,
+            height: (*rows * BLOCK) as u16,
+            buffer: pixels.into(),
+            delay: self.delay,
+            ..gif::Frame::default()
+        };
+        let written = encoder.write_frame(&frame);
+        written.map_err(|e| self.failed(e))
+    }
+
+    /// cells across or down, in pixels, as long as a GIF can be that big
+    fn dimension(&self, cells: usize) -> AResult<u16> {
+        u16::try_from(cells * BLOCK)
+            .map_err(|_| self.failed(format!("{cells} cells is too many for a GIF")))
+    }
+
+    fn failed(&self, reason: impl std::fmt::Display) -> Error {
+        Error::solver(format!("{} not recorded :: {reason}", self.path.display()))
+    }
+}
+
+impl Visualiser for GifRecorder {
+    fn frame(&mut self, frame: &Frame) -> AResult<()> {
+        match self.skipper.keeps(frame) {
+            true => self.record(frame),
+            false => Ok(()),
+        }
+    }
+
+    fn event(&mut self, _: &str) -> AResult<()> {
+        Ok(())
+    }
+
+    fn finish(&mut self) -> AResult<()> {
+        if let Some(last) = self.skipper.last() {
+            self.record(&last)?;
+        }
+        if self.encoder.is_none() {
+            // a run without frames still leaves a picture behind, if an empty one
+            self.record(&Frame::new())?;
+        }
+        let (encoder, _, _) = self.encoder.take().expect("recorded above");
+        let flushed = encoder.into_inner().and_then(|mut out| out.flush());
+        flushed.map_err(|e| self.failed(e))
+    }
+}
+
+/// Which colour of the palette a cell is drawn in
+fn colour(cell: &Cell) -> u8 {
+    match cell.style {
+        Style::Plain if matches!(cell.symbol, ' ' | '.' | '·') => 0,
+        Style::Plain => 1,
+        Style::Faint => 2,
+        Style::Wall => 3,
+        Style::Path => 4,
+        Style::SidePath => 5,
+        Style::Marker => 6,
+        Style::Highlight => 7,
+    }
+}
+
+/// An asciinema recording (asciicast v2) of the frames in colour, with the solver's
+/// events as markers, written as the frames come so a long run is not held in memory
+pub struct CastRecorder {
+    path: PathBuf,
+    title: String,
+    skipper: Skipper,
+    fps: u32,
+    /// the frames recorded so far, which sets the time of the next
+    recorded: usize,
+    out: Option<BufWriter<File>>,
+}
+
+impl CastRecorder {
+    pub fn new(path: impl Into<PathBuf>, title: &str, pace: Pace) -> Self {
+        Self {
+            path: path.into(),
+            title: title.to_string(),
+            skipper: Skipper::new(pace),
+            fps: pace.fps.max(1),
+            recorded: 0,
+            out: None,
+        }
+    }
+
+    /// seconds into the recording of the latest frame
+    fn time(&self) -> f64 {
+        self.recorded.saturating_sub(1) as f64 / self.fps as f64
+    }
+
+    fn record(&mut self, frame: &Frame) -> AResult<()> {
+        let text = frame.text();
+        let screen = format!("\x1b[H\x1b[2J{}", frame.coloured()).replace('\n', "\r\n");
+        if self.out.is_none() {
+            let width = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+            let header = format!(
+                "{{\"version\": 2, \"width\": {}, \"height\": {}, \"title\": {}}}",
+                width.max(1),
+                text.lines().count().max(1),
+                json(&self.title)
+            );
+            self.out = Some(create(&self.path)?);
+            self.write(&header)?;
+        }
+        self.recorded += 1;
+        self.write(&format!("[{:.3}, \"o\", {}]", self.time(), json(&screen)))
+    }
+
+    fn write(&mut self, line: &str) -> AResult<()> {
+        let out = self.out.as_mut().expect("created with the first frame");
+        writeln!(out, "{line}").map_err(|error| Error::Io {
+            path: self.path.clone(),
+            error,
+        })
+    }
+}
+
+impl Visualiser for CastRecorder {
+    fn frame(&mut self, frame: &Frame) -> AResult<()> {
+        match self.skipper.keeps(frame) {
+            true => self.record(frame),
+            false => Ok(()),
+        }
+    }
+
+    fn event(&mut self, text: &str) -> AResult<()> {
+        match self.out {
+            Some(_) => self.write(&format!("[{:.3}, \"m\", {}]", self.time(), json(text))),
+            // nothing to mark yet
+            None => Ok(()),
+        }
+    }
+
+    fn finish(&mut self) -> AResult<()> {
+        if let Some(last) = self.skipper.last() {
+            self.record(&last)?;
+        }
+        if self.out.is_none() {
+            self.record(&Frame::new())?;
+        }
+        let mut out = self.out.take().expect("recorded above");
+        out.flush().map_err(|error| Error::Io {
+            path: self.path.clone(),
+            error,
+        })
+    }
+}
+
+/// The text as a JSON string
+fn json(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            '\r' => quoted += "\\r",
+            '\t' => quoted += "\\t",
+            c if c.is_control() => quoted += &format!("\\u{:04x}", c as u32),
+            c => quoted.push(c),
+        }
+    }
+    quoted + "\""
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn numbered(n: usize) -> Frame {
+        Frame::from_iter([vec![
+            Cell::new('#', Style::Wall),
+            Cell::plain('.'),
+            Cell::new('S', Style::Marker),
+        ]])
+        .with_caption(format!("step {n}"))
+    }
+
+    fn temp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("aoc-record-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn records_casts_at_the_pace_given() {
+        let path = temp("walk.cast");
+        let mut cast = CastRecorder::new(&path, "day6 \"test\"", Pace { fps: 4, every: 2 });
+        for n in 0..4 {
+            cast.frame(&numbered(n)).unwrap();
+            if n == 2 {
+                cast.event("loop at 1,2").unwrap();
+            }
+        }
+        cast.finish().unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "{\"version\": 2, \"width\": 6, \"height\": 2, \"title\": \"day6 \\\"test\\\"\"}",
+                "[0.000, \"o\", \"\\u001b[H\\u001b[2J\\u001b[40;90m#\\u001b[0m.\\u001b[30;103mS\\u001b[0m\\r\\nstep 0\\r\\n\"]",
+            ],
+            lines[..2]
+        );
+        assert!(lines[2].starts_with("[0.250, \"o\", ") && lines[2].contains("step 2"));
+        assert_eq!("[0.250, \"m\", \"loop at 1,2\"]", lines[3]);
+        // the last frame is kept though it was due to be skipped
+        assert!(lines[4].starts_with("[0.500, \"o\", ") && lines[4].contains("step 3"));
+        assert_eq!(5, lines.len());
+    }
+
+    #[test]
+    fn records_gifs_a_block_per_cell() {
+        let path = temp("walk.gif");
+        let mut gif = GifRecorder::new(&path, Pace { fps: 20, every: 3 });
+        for n in 0..5 {
+            gif.frame(&numbered(n)).unwrap();
+        }
+        gif.finish().unwrap();
+
+        let file = File::open(&path).unwrap();
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(file).unwrap();
+        assert_eq!((12, 4), (decoder.width(), decoder.height()));
+
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push((frame.delay, frame.buffer.to_vec()));
+        }
+        std::fs::remove_file(path).unwrap();
+
+        // frames 0 and 3 as they came, then 4 for the end
+        assert_eq!(3, frames.len());
+        let row = [[3; BLOCK], [0; BLOCK], [6; BLOCK]].concat();
+        assert_eq!((5, row.repeat(BLOCK)), frames[0]);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::answer::Answer;
+use crate::config::{read_config, Part};
+use crate::files::{self, Input};
+use crate::history::History;
+use crate::misc::AResult;
+use crate::solution::Solution;
+use std::time::{Duration, Instant};
+
+/// How long each stage of a run took
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timings {
+    pub io: Duration,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.io + self.parse + self.solve
+    }
+}
+
+impl std::fmt::Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "io {:.2?}, parse {:.2?}, solve {:.2?}",
+            self.io, self.parse, self.solve
+        )
+    }
+}
+
+/// Load the input, from its file or the config, and solve one part of it
+pub fn run<S: Solution>(input: &Input, part: u8) -> AResult<Answer> {
+    run_timed::<S>(input, part).map(|(answer, _)| answer)
+}
+
+/// As run, timing the loading, parsing and solving separately
+pub fn run_timed<S: Solution>(input: &Input, part: u8) -> AResult<(Answer, Timings)> {
+    let in_file = |e: crate::error::Error| e.in_file(&input.to_string());
+
+    let now = Instant::now();
+    let text = files::load_input_with(input, S::NORMALISE)?;
+    let io = now.elapsed();
+
+    let now = Instant::now();
+    let parsed = S::parse(&text).map_err(in_file)?;
+    let parse = now.elapsed();
+
+    let now = Instant::now();
+    let answer = S::solve_parsed(&parsed, part).map_err(in_file)?;
+    let solve = now.elapsed();
+
+    Ok((answer.into(), Timings { io, parse, solve }))
+}
+
+/// Run every active line of the day's config, printing the results,
+/// failing only when the config can't be read
+pub fn run_day<S: Solution>(day: &str) -> AResult<()> {
+    let parts = read_config(day)?;
+
+    if parts.is_empty() {
+        println!("no active lines found in the config file");
+    }
+
+    let history = History::load().unwrap_or_else(|e| {
+        println!("answer history not read :: {e}");
+        History::default()
+    });
+
+    for part in &parts {
+        println!(
+            "Running day {} part {} using {} data",
+            day, part.part, part.mode
+        );
+        match generate_result::<S>(part) {
+            Ok(result) => {
+                if let Some(warning) = history.check_case(day, part, &result) {
+                    println!("warning: {warning}");
+                }
+                println!("result: {result}")
+            }
+            Err(e) => println!(
+                "{} failed for part {} <{}> :: {}",
+                day, part.part, part.input, e
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn generate_result<S: Solution>(part: &Part) -> AResult<Answer> {
+    let (result, timings) = run_timed::<S>(&part.input, part.part)?;
+    println!("Elapsed: {:.2?} ({timings})", timings.total());
+    Ok(result)
+}
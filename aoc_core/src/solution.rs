@@ -0,0 +1,31 @@
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::files::Normalise;
+use crate::misc::AResult;
+
+/// A day's puzzle, split so the input is parsed once and either part solved from it.
+/// Each day crate exports a unit struct `Solver` implementing this.
+pub trait Solution {
+    /// the parsed puzzle input shared by both parts
+    type Input;
+    type Answer: Into<Answer>;
+
+    /// how the input is tidied before it is parsed
+    const NORMALISE: Normalise = Normalise { trim_lines: false };
+
+    fn parse(input: &str) -> AResult<Self::Input>;
+    fn part1(input: &Self::Input) -> AResult<Self::Answer>;
+    fn part2(input: &Self::Input) -> AResult<Self::Answer>;
+
+    fn solve_parsed(input: &Self::Input, part: u8) -> AResult<Self::Answer> {
+        match part {
+            1 => Self::part1(input),
+            2 => Self::part2(input),
+            _ => Err(Error::solver(format!("unknown part {part}"))),
+        }
+    }
+
+    fn solve(input: &str, part: u8) -> AResult<Self::Answer> {
+        Self::solve_parsed(&Self::parse(input)?, part)
+    }
+}
@@ -0,0 +1,263 @@
+//! Scalable pictures of a grid, for inputs too big for the terminal and for sharing.
+//! A picture is the grid's size with layers of cells drawn over it, each layer in its
+//! own group so it can be picked out, restyled or hidden in an editor afterwards.
+
+use crate::error::Error;
+use crate::misc::AResult;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Units along each side of a cell
+const CELL: usize = 10;
+
+const BACKGROUND: &str = "#101014";
+const LABEL: &str = "#d0d0d0";
+
+/// What a set of cells stands for, the layers drawn in the order given here
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Layer {
+    Walls,
+    /// another route worth seeing beside the main one
+    SecondaryPath,
+    PrimaryPath,
+    Highlights,
+    /// drawn as rings, leaving whatever is under them in sight
+    Antinodes,
+    Start,
+    End,
+}
+
+impl Layer {
+    fn id(self) -> &'static str {
+        match self {
+            Layer::Walls => "walls",
+            Layer::SecondaryPath => "secondary-path",
+            Layer::PrimaryPath => "primary-path",
+            Layer::Highlights => "highlights",
+            Layer::Antinodes => "antinodes",
+            Layer::Start => "start",
+            Layer::End => "end",
+        }
+    }
+
+    fn colour(self) -> &'static str {
+        match self {
+            Layer::Walls => "#707078",
+            Layer::SecondaryPath => "#2ab7ca",
+            Layer::PrimaryPath => "#3cb043",
+            Layer::Highlights => "#e040fb",
+            Layer::Antinodes => "#ff5a36",
+            Layer::Start => "#ffdd33",
+            Layer::End => "#ff9f1c",
+        }
+    }
+}
+
+/// What a cell is drawn as, a cell may have several
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mark {
+    On(Layer),
+    /// written over whatever layers the cell is on
+    Label(char),
+}
+
+/// A grid to be drawn, `rows` by `cols` cells, with the layers added to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Svg {
+    rows: usize,
+    cols: usize,
+    layers: BTreeMap<Layer, Vec<(usize, usize)>>,
+    /// a symbol written in a cell, such as an antenna's frequency
+    labels: Vec<(usize, usize, char)>,
+}
+
+impl Svg {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            layers: BTreeMap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    /// A picture of any grid, `mark` saying what each cell, given as row and column,
+    /// is drawn as. More layers can be added over it.
+    pub fn from_grid<M: IntoIterator<Item = Mark>>(
+        rows: usize,
+        cols: usize,
+        mark: impl Fn((usize, usize)) -> M,
+    ) -> Self {
+        let mut svg = Svg::new(rows, cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                for m in mark((row, col)) {
+                    match m {
+                        Mark::On(layer) => svg.layers.entry(layer).or_default().push((row, col)),
+                        Mark::Label(symbol) => svg.labels.push((row, col, symbol)),
+                    }
+                }
+            }
+        }
+        svg
+    }
+
+    /// Adds the cells, given as row and column, to the layer
+    pub fn with(mut self, layer: Layer, cells: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        self.layers.entry(layer).or_default().extend(cells);
+        self
+    }
+
+    pub fn with_labels(mut self, labels: impl IntoIterator<Item = (usize, usize, char)>) -> Self {
+        self.labels.extend(labels);
+        self
+    }
+
+    pub fn render(&self) -> String {
+        let (width, height) = (self.cols * CELL, self.rows * CELL);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {width} {height}\" width=\"{width}\" height=\"{height}\">\n\
+             <rect width=\"{width}\" height=\"{height}\" fill=\"{BACKGROUND}\"/>\n"
+        );
+
+        for (&layer, cells) in self.layers.iter().filter(|(_, cells)| !cells.is_empty()) {
+            let (id, colour) = (layer.id(), layer.colour());
+            match layer {
+                Layer::Antinodes => {
+                    let _ = writeln!(
+                        svg,
+                        "<g id=\"{id}\" fill=\"none\" stroke=\"{colour}\" stroke-width=\"1.5\">"
+                    );
+                    for (row, col) in sorted(cells) {
+                        let (x, y) = (col * CELL + CELL / 2, row * CELL + CELL / 2);
+                        let _ = writeln!(svg, "<circle cx=\"{x}\" cy=\"{y}\" r=\"3.5\"/>");
+                    }
+                }
+                _ => {
+                    let _ = writeln!(svg, "<g id=\"{id}\" fill=\"{colour}\">");
+                    for (row, col, length) in runs(cells) {
+                        let _ = writeln!(
+                            svg,
+                            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{CELL}\"/>",
+                            col * CELL,
+                            row * CELL,
+                            length * CELL
+                        );
+                    }
+                }
+            }
+            svg += "</g>\n";
+        }
+
+        if !self.labels.is_empty() {
+            let _ = writeln!(
+                svg,
+                "<g id=\"labels\" fill=\"{LABEL}\" font-family=\"monospace\" font-size=\"8\" text-anchor=\"middle\" dominant-baseline=\"central\">"
+            );
+            let mut labels = self.labels.clone();
+            labels.sort_unstable();
+            for (row, col, symbol) in labels {
+                let (x, y) = (col * CELL + CELL / 2, row * CELL + CELL / 2);
+                let _ = writeln!(svg, "<text x=\"{x}\" y=\"{y}\">{}</text>", escape(symbol));
+            }
+            svg += "</g>\n";
+        }
+
+        svg + "</svg>\n"
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> AResult<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.render()).map_err(|error| Error::Io {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+}
+
+/// The cells as runs along their rows, each a row, the column it starts at and how many
+/// cells it covers, so a big maze's walls are a rectangle per stretch rather than per cell
+fn runs(cells: &[(usize, usize)]) -> Vec<(usize, usize, usize)> {
+    let mut runs: Vec<(usize, usize, usize)> = Vec::new();
+    for (row, col) in sorted(cells) {
+        match runs.last_mut() {
+            Some((r, c, length)) if *r == row && *c + *length == col => *length += 1,
+            _ => runs.push((row, col, 1)),
+        }
+    }
+    runs
+}
+
+/// in reading order, so the same picture is always written the same way
+fn sorted(cells: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut cells = cells.to_vec();
+    cells.sort_unstable();
+    cells.dedup();
+    cells
+}
+
+fn escape(symbol: char) -> String {
+    match symbol {
+        '&' => "&amp;".to_string(),
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        c => c.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn draws_the_layers_in_order() {
+        let svg = Svg::new(2, 3)
+            .with(Layer::End, [(0, 2)])
+            .with(Layer::Walls, [(1, 1), (1, 0), (0, 0), (1, 2)])
+            .with(Layer::PrimaryPath, [])
+            .with(Layer::Antinodes, [(0, 1)])
+            .with_labels([(1, 1, '<'), (0, 1, 'a')]);
+
+        assert_eq!(
+            "\
+<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 30 20\" width=\"30\" height=\"20\">
+<rect width=\"30\" height=\"20\" fill=\"#101014\"/>
+<g id=\"walls\" fill=\"#707078\">
+<rect x=\"0\" y=\"0\" width=\"10\" height=\"10\"/>
+<rect x=\"0\" y=\"10\" width=\"30\" height=\"10\"/>
+</g>
+<g id=\"antinodes\" fill=\"none\" stroke=\"#ff5a36\" stroke-width=\"1.5\">
+<circle cx=\"15\" cy=\"5\" r=\"3.5\"/>
+</g>
+<g id=\"end\" fill=\"#ff9f1c\">
+<rect x=\"20\" y=\"0\" width=\"10\" height=\"10\"/>
+</g>
+<g id=\"labels\" fill=\"#d0d0d0\" font-family=\"monospace\" font-size=\"8\" text-anchor=\"middle\" dominant-baseline=\"central\">
+<text x=\"15\" y=\"5\">a</text>
+<text x=\"15\" y=\"15\">&lt;</text>
+</g>
+</svg>
+",
+            svg.render()
+        );
+    }
+
+    #[test]
+    fn draws_a_grid_cell_by_cell() {
+        let grid = ["#a", ".#"];
+        let svg = Svg::from_grid(2, 2, |(row, col)| match grid[row].as_bytes()[col] {
+            b'#' => vec![Mark::On(Layer::Walls)],
+            b'.' => vec![],
+            c => vec![Mark::On(Layer::Antinodes), Mark::Label(char::from(c))],
+        });
+
+        assert_eq!(
+            Svg::new(2, 2)
+                .with(Layer::Walls, [(0, 0), (1, 1)])
+                .with(Layer::Antinodes, [(0, 1)])
+                .with_labels([(0, 1, 'a')]),
+            svg
+        );
+    }
+}
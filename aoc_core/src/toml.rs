@@ -0,0 +1,426 @@
+//! The day configs, answer history and benchmark baseline, read with the toml crate
+//! and laid out as the tables and keys they are written as, each remembering where it was.
+//! Integers are kept as their decimal digits so each field can decide how wide it needs to be.
+//! The parser carries on past an error so one pass finds every syntax error.
+
+use crate::error::{Diagnostic, Error};
+use crate::misc::AResult;
+use ::toml::de::{DeTable, DeValue};
+use ::toml::Spanned;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(String),
+    Array(Vec<Value>),
+    /// a float, boolean, date or table, which nothing here reads, named by its kind
+    Other(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub key: String,
+    pub value: Value,
+    pub line: usize,
+    pub column: usize,
+    pub value_column: usize,
+    /// where the value is written, in bytes
+    pub span: Range<usize>,
+}
+
+/// One `[name]` or `[[name]]` section, keys before the first header land in a table named ""
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub name: String,
+    pub line: usize,
+    pub column: usize,
+    pub entries: Vec<Entry>,
+}
+
+impl Value {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Value::String(_) => "a string",
+            Value::Integer(_) => "an integer",
+            Value::Array(_) => "an array",
+            Value::Other(kind) => kind,
+        }
+    }
+}
+
+/// The tables that could be read, along with everything wrong with the rest
+pub fn parse(file: &str, text: &str) -> (Vec<Table>, Vec<Diagnostic>) {
+    let (document, errors) = DeTable::parse_recoverable(text);
+    let reader = Reader { file, text };
+
+    let mut diagnostics = errors
+        .iter()
+        .map(|e| {
+            let (line, column) = reader.position(e.span().map_or(0, |span| span.start));
+            reader.diagnostic(line, column, e.message().trim())
+        })
+        .collect::<Vec<_>>();
+
+    let mut root = Table {
+        name: String::new(),
+        line: 1,
+        column: 1,
+        entries: Vec::new(),
+    };
+    let mut tables = Vec::new();
+    for (key, value) in document.get_ref() {
+        match value.get_ref() {
+            DeValue::Table(table) => {
+                tables.push(reader.table(key.get_ref(), value.span(), table, &mut diagnostics))
+            }
+            DeValue::Array(array)
+                if !array.is_empty() && array.iter().all(|v| v.get_ref().is_table()) =>
+            {
+                for element in array {
+                    if let DeValue::Table(table) = element.get_ref() {
+                        tables.push(reader.table(
+                            key.get_ref(),
+                            element.span(),
+                            table,
+                            &mut diagnostics,
+                        ));
+                    }
+                }
+            }
+            _ => root
+                .entries
+                .push(reader.entry(key, value, &mut diagnostics)),
+        }
+    }
+    tables.sort_by_key(|table| (table.line, table.column));
+    tables.insert(0, root);
+
+    (tables, diagnostics)
+}
+
+/// A TOML basic string, written with the short escapes
+pub fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            '\t' => quoted += "\\t",
+            '\r' => quoted += "\\r",
+            c if c.is_control() => quoted.push(' '),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Looks up the keys of one table, every key being required
+pub(crate) struct Fields<'a> {
+    pub file: &'a str,
+    pub table: &'a Table,
+}
+
+impl Fields<'_> {
+    pub fn value(&self, key: &str) -> AResult<&Value> {
+        self.table
+            .entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| &entry.value)
+            .ok_or_else(|| {
+                Error::config(
+                    self.file,
+                    Some(self.table.line),
+                    format!("[[{}]] is missing '{key}'", self.table.name),
+                )
+            })
+    }
+
+    pub fn string(&self, key: &str) -> AResult<String> {
+        match self.value(key)? {
+            Value::String(s) => Ok(s.clone()),
+            other => Err(self.wrong_kind(key, "a string", other)),
+        }
+    }
+
+    pub fn number<T: std::str::FromStr>(&self, key: &str) -> AResult<T> {
+        match self.value(key)? {
+            Value::Integer(raw) => raw.parse().map_err(|_| {
+                Error::config(
+                    self.file,
+                    Some(self.table.line),
+                    format!("'{key}' is out of range, {raw}"),
+                )
+            }),
+            other => Err(self.wrong_kind(key, "an integer", other)),
+        }
+    }
+
+    pub fn wrong_kind(&self, key: &str, expected: &str, found: &Value) -> Error {
+        Error::config(
+            self.file,
+            Some(self.table.line),
+            format!("'{key}' should be {expected}, not {}", found.kind()),
+        )
+    }
+}
+
+struct Reader<'a> {
+    file: &'a str,
+    text: &'a str,
+}
+
+impl Reader<'_> {
+    fn diagnostic(&self, line: usize, column: usize, reason: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            file: self.file.to_string(),
+            line,
+            column,
+            reason: reason.into(),
+        }
+    }
+
+    /// 1-based line and char column of a byte offset
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let before = &self.text[..offset.min(self.text.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
+
+    /// `span` is the table's header, or its key when it has none
+    fn table(
+        &self,
+        name: &str,
+        span: Range<usize>,
+        table: &DeTable,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Table {
+        let (line, column) = self.position(span.start);
+        Table {
+            name: name.to_string(),
+            line,
+            column,
+            entries: table
+                .iter()
+                .map(|(key, value)| self.entry(key, value, diagnostics))
+                .collect(),
+        }
+    }
+
+    fn entry(
+        &self,
+        key: &Spanned<std::borrow::Cow<str>>,
+        value: &Spanned<DeValue>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Entry {
+        let (line, column) = self.position(key.span().start);
+        let span = value.span();
+        let (value_line, value_column) = self.position(span.start);
+        let value = self.value(value.get_ref()).unwrap_or_else(|reason| {
+            diagnostics.push(self.diagnostic(value_line, value_column, reason));
+            Value::Other("an integer")
+        });
+        Entry {
+            key: key.get_ref().to_string(),
+            value,
+            line,
+            column,
+            value_column,
+            span,
+        }
+    }
+
+    fn value(&self, value: &DeValue) -> Result<Value, String> {
+        Ok(match value {
+            DeValue::String(s) => Value::String(s.to_string()),
+            DeValue::Integer(i) if i.radix() == 10 => {
+                Value::Integer(i.as_str().trim_start_matches('+').to_string())
+            }
+            DeValue::Integer(i) => Value::Integer(
+                u128::from_str_radix(i.as_str(), i.radix())
+                    .map_err(|_| format!("'{}' is too big", i.as_str()))?
+                    .to_string(),
+            ),
+            DeValue::Array(values) => Value::Array(
+                values
+                    .iter()
+                    .map(|v| self.value(v.get_ref()))
+                    .collect::<Result<_, _>>()?,
+            ),
+            DeValue::Float(_) => Value::Other("a float"),
+            DeValue::Boolean(_) => Value::Other("a boolean"),
+            DeValue::Datetime(_) => Value::Other("a date"),
+            DeValue::Table(_) => Value::Other("a table"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_array_of_tables() {
+        let (tables, diagnostics) = parse(
+            "day1.toml",
+            r#"# comment
+[[case]]
+name = "example" # trailing comment
+expected = 1_000
+tags = ["slow", 'raw\n']
+
+[[case]]
+expected = -17
+"#,
+        );
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(3, tables.len());
+        assert_eq!(("case", 2), (tables[1].name.as_str(), tables[1].line));
+        assert_eq!(
+            Value::String("example".to_string()),
+            tables[1].entries[0].value
+        );
+        assert_eq!(
+            Value::Integer("1000".to_string()),
+            tables[1].entries[1].value
+        );
+        assert_eq!(
+            Value::Array(vec![
+                Value::String("slow".to_string()),
+                Value::String("raw\\n".to_string())
+            ]),
+            tables[1].entries[2].value
+        );
+        assert_eq!(
+            Value::Integer("-17".to_string()),
+            tables[2].entries[0].value
+        );
+        assert_eq!((8, 1, 12), {
+            let e = &tables[2].entries[0];
+            (e.line, e.column, e.value_column)
+        });
+    }
+
+    #[test]
+    fn parses_multi_line_strings() {
+        let (tables, diagnostics) = parse(
+            "day1.toml",
+            "basic = \"\"\"\n#.#\\t\n#\"#\n\"\"\"\nliteral = \'\'\'a\\n\nb\'\'\'\nafter = 1\n",
+        );
+
+        assert!(diagnostics.is_empty());
+        let values = tables[0]
+            .entries
+            .iter()
+            .map(|e| (e.key.as_str(), e.line, e.value.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("basic", 1, Value::String("#.#\t\n#\"#\n".to_string())),
+                ("literal", 5, Value::String("a\\n\nb".to_string())),
+                ("after", 7, Value::Integer("1".to_string())),
+            ],
+            values
+        );
+
+        let (_, diagnostics) = parse("day1.toml", "a = 1\nb = \"\"\"\nnever = closed\n[[case]]\n");
+        assert_eq!(
+            vec!["<day1.toml:5:1> :: invalid multi-line basic string, expected `\"`"],
+            diagnostics
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn reads_lines_that_look_like_toml_inside_strings() {
+        let (tables, diagnostics) = parse(
+            "day1.toml",
+            "[[case]]\ninput_text = \"\"\"\na = b \\\n    continued\n[[case]]\n\"\"\"\nname = \"a\"\n",
+        );
+
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+        assert_eq!(2, tables.len());
+        assert_eq!(
+            vec![
+                (
+                    "input_text",
+                    2,
+                    Value::String("a = b continued\n[[case]]\n".to_string())
+                ),
+                ("name", 7, Value::String("a".to_string())),
+            ],
+            tables[1]
+                .entries
+                .iter()
+                .map(|e| (e.key.as_str(), e.line, e.value.clone()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn reads_dotted_keys_and_inline_tables() {
+        let values = |text: &str| {
+            let (tables, diagnostics) = parse("day1.toml", text);
+            assert!(diagnostics.is_empty(), "{diagnostics:?}");
+            tables
+                .iter()
+                .filter(|t| t.name == "case")
+                .map(|t| {
+                    let values = t.entries.iter().map(|e| e.value.clone());
+                    (t.line, values.collect::<Vec<_>>())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec![(
+                1,
+                vec![
+                    Value::String("a".to_string()),
+                    Value::Integer("2".to_string())
+                ]
+            )],
+            values("case = [{ name = \"a\", part = 0x2 }]\n")
+        );
+        assert_eq!(
+            vec![(
+                1,
+                vec![Value::String("b".to_string()), Value::Other("a table")]
+            )],
+            values("[[case]]\nname = \"b\"\nnotes.by = \"me\"\n")
+        );
+    }
+
+    #[test]
+    fn reports_every_bad_line() {
+        let (tables, diagnostics) = parse(
+            "day1.toml",
+            "[[case]]\nname = \"open\npart = 1 2\nmode = \"test\"\n",
+        );
+
+        assert_eq!(
+            vec![
+                "<day1.toml:2:13> :: invalid basic string, expected `\"`",
+                "<day1.toml:3:8> :: string values must be quoted, expected literal string",
+            ],
+            diagnostics
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+        );
+        let mode = tables[1].entries.iter().find(|e| e.key == "mode");
+        assert_eq!(
+            Some(&Value::String("test".to_string())),
+            mode.map(|e| &e.value)
+        );
+    }
+}
@@ -0,0 +1,514 @@
+//! Pictures of a solver at work. Solvers push frames, and events between them, to the
+//! visualiser the runner put in place for their thread. With none in place, or one that
+//! shows nothing, the frames are never drawn, so a solver can push them unconditionally.
+
+use crate::error::Error;
+use crate::misc::AResult;
+use crate::record::{CastRecorder, GifRecorder, Pace};
+use std::cell::RefCell;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long the terminal animation holds each frame unless told otherwise
+pub const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+/// What a cell stands for, each backend choosing how to show it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Style {
+    #[default]
+    Plain,
+    /// ground already covered, or anything else the eye can skip
+    Faint,
+    Wall,
+    /// the route found
+    Path,
+    /// another route worth seeing beside the main one
+    SidePath,
+    /// a start, an end, or whoever is moving
+    Marker,
+    Highlight,
+}
+
+impl Style {
+    /// the SGR parameters the terminal animation draws the style with
+    fn ansi(self) -> Option<&'static str> {
+        match self {
+            Style::Plain => None,
+            Style::Faint => Some("90"),
+            Style::Wall => Some("40;90"),
+            Style::Path => Some("42;30"),
+            Style::SidePath => Some("46;30"),
+            Style::Marker => Some("30;103"),
+            Style::Highlight => Some("7"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub style: Style,
+}
+
+impl Cell {
+    pub fn new(symbol: char, style: Style) -> Self {
+        Self { symbol, style }
+    }
+
+    pub fn plain(symbol: char) -> Self {
+        Self::new(symbol, Style::Plain)
+    }
+}
+
+/// One picture: rows of cells with a line of text under them
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Frame {
+    rows: Vec<Vec<Cell>>,
+    caption: String,
+}
+
+impl Frame {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = Cell>) {
+        self.rows.push(row.into_iter().collect());
+    }
+
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    pub fn rows(&self) -> &[Vec<Cell>] {
+        &self.rows
+    }
+
+    pub fn caption(&self) -> &str {
+        &self.caption
+    }
+
+    /// The symbols alone, a line per row, then the caption if there is one
+    pub fn text(&self) -> String {
+        self.lines(|cell, text| text.push(cell.symbol))
+    }
+
+    /// As text, each styled cell wrapped in its ANSI colours
+    pub fn coloured(&self) -> String {
+        self.lines(|cell, text| match cell.style.ansi() {
+            Some(sgr) => *text += &format!("\x1b[{sgr}m{}\x1b[0m", cell.symbol),
+            None => text.push(cell.symbol),
+        })
+    }
+
+    fn lines(&self, mut draw: impl FnMut(&Cell, &mut String)) -> String {
+        let mut text = String::new();
+        for row in &self.rows {
+            row.iter().for_each(|cell| draw(cell, &mut text));
+            text.push('\n');
+        }
+        if !self.caption.is_empty() {
+            text += &self.caption;
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl FromIterator<Vec<Cell>> for Frame {
+    fn from_iter<I: IntoIterator<Item = Vec<Cell>>>(rows: I) -> Self {
+        Self {
+            rows: rows.into_iter().collect(),
+            caption: String::new(),
+        }
+    }
+}
+
+/// Somewhere for a solver's frames and events to go
+pub trait Visualiser {
+    /// false for a visualiser that shows nothing, so frames need not be drawn for it
+    fn is_shown(&self) -> bool {
+        true
+    }
+    fn frame(&mut self, frame: &Frame) -> AResult<()>;
+    /// something worth saying between frames, such as a loop found
+    fn event(&mut self, text: &str) -> AResult<()>;
+    /// called once the solver is done
+    fn finish(&mut self) -> AResult<()> {
+        Ok(())
+    }
+}
+
+/// Shows nothing
+pub struct Hidden;
+
+impl Visualiser for Hidden {
+    fn is_shown(&self) -> bool {
+        false
+    }
+    fn frame(&mut self, _: &Frame) -> AResult<()> {
+        Ok(())
+    }
+    fn event(&mut self, _: &str) -> AResult<()> {
+        Ok(())
+    }
+}
+
+/// Every frame written out in turn without colour, events as lines between them
+pub struct PlainText<W: Write> {
+    out: W,
+}
+
+impl<W: Write> PlainText<W> {
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+}
+
+impl<W: Write> Visualiser for PlainText<W> {
+    fn frame(&mut self, frame: &Frame) -> AResult<()> {
+        writeln!(self.out, "{}", frame.text()).map_err(written("frame"))
+    }
+
+    fn event(&mut self, text: &str) -> AResult<()> {
+        writeln!(self.out, "{text}").map_err(written("event"))
+    }
+
+    fn finish(&mut self) -> AResult<()> {
+        self.out.flush().map_err(written("frames"))
+    }
+}
+
+/// Each frame drawn in colour over the last one on stdout, held for the delay.
+/// The latest event is shown under the frame until the next one replaces it.
+pub struct Animation {
+    delay: Duration,
+    last: Option<String>,
+    /// how many lines the last frame took, to go back up over it
+    lines: usize,
+    event: String,
+}
+
+impl Animation {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            last: None,
+            lines: 0,
+            event: String::new(),
+        }
+    }
+
+    fn draw(&mut self) -> AResult<()> {
+        let Some(text) = &self.last else {
+            return Ok(());
+        };
+        let mut out = std::io::stdout().lock();
+        if self.lines > 0 {
+            // back to the top of the last frame, clearing everything below it
+            write!(out, "\x1b[{}A\x1b[J", self.lines).map_err(written("frame"))?;
+        }
+        writeln!(out, "{text}{}", self.event).map_err(written("frame"))?;
+        out.flush().map_err(written("frame"))?;
+        self.lines = text.lines().count() + 1;
+        Ok(())
+    }
+}
+
+impl Visualiser for Animation {
+    fn frame(&mut self, frame: &Frame) -> AResult<()> {
+        self.last = Some(frame.coloured());
+        self.draw()?;
+        std::thread::sleep(self.delay);
+        Ok(())
+    }
+
+    fn event(&mut self, text: &str) -> AResult<()> {
+        self.event = text.to_string();
+        self.draw()
+    }
+}
+
+/// Every frame saved as its own text file in a directory, `frame-00001.txt` onwards,
+/// with the events in `events.txt` numbered by the frame they followed
+pub struct FrameDump {
+    dir: PathBuf,
+    count: usize,
+}
+
+impl FrameDump {
+    pub fn new(dir: impl Into<PathBuf>) -> AResult<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).map_err(|error| Error::Io {
+            path: dir.clone(),
+            error,
+        })?;
+        Ok(Self { dir, count: 0 })
+    }
+}
+
+impl Visualiser for FrameDump {
+    fn frame(&mut self, frame: &Frame) -> AResult<()> {
+        self.count += 1;
+        let path = self.dir.join(format!("frame-{:05}.txt", self.count));
+        std::fs::write(&path, frame.text()).map_err(|error| Error::Io { path, error })
+    }
+
+    fn event(&mut self, text: &str) -> AResult<()> {
+        let path = self.dir.join("events.txt");
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{:05} {text}", self.count))
+            .map_err(|error| Error::Io { path, error })
+    }
+}
+
+fn written(what: &'static str) -> impl Fn(std::io::Error) -> Error {
+    move |e| Error::solver(format!("{what} not shown :: {e}"))
+}
+
+/// Which visualiser a run puts in place, as given to `aoc run --visual`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Visual {
+    #[default]
+    None,
+    Plain,
+    /// the animation, holding each frame for the delay
+    Terminal(Duration),
+    /// frames saved under the directory, a directory per case
+    Dump(PathBuf),
+    /// an animated GIF in the directory for each case, see record
+    Gif(PathBuf, Pace),
+    /// an asciinema recording in the directory for each case
+    Cast(PathBuf, Pace),
+}
+
+impl Visual {
+    /// Plain text and the animation both take over the terminal, so only one case at a
+    /// time can be shown with them
+    pub fn needs_terminal(&self) -> bool {
+        matches!(self, Visual::Plain | Visual::Terminal(_))
+    }
+
+    /// The same, recording at the pace given. Only recordings have a pace.
+    pub fn paced(self, pace: Pace) -> Result<Self, String> {
+        match self {
+            Visual::Gif(dir, _) => Ok(Visual::Gif(dir, pace)),
+            Visual::Cast(dir, _) => Ok(Visual::Cast(dir, pace)),
+            _ => Err("a frame rate or frames to skip need a gif or cast recording".to_string()),
+        }
+    }
+
+    /// The visualiser for one case, `case` naming its directory or recording when
+    /// frames are saved
+    pub fn visualiser(&self, case: &str) -> AResult<Box<dyn Visualiser>> {
+        Ok(match self {
+            Visual::None => Box::new(Hidden),
+            Visual::Plain => Box::new(PlainText::new(std::io::stdout())),
+            Visual::Terminal(delay) => Box::new(Animation::new(*delay)),
+            Visual::Dump(dir) => Box::new(FrameDump::new(dir.join(case))?),
+            Visual::Gif(dir, pace) => {
+                Box::new(GifRecorder::new(dir.join(case.to_string() + ".gif"), *pace))
+            }
+            Visual::Cast(dir, pace) => Box::new(CastRecorder::new(
+                dir.join(case.to_string() + ".cast"),
+                case,
+                *pace,
+            )),
+        })
+    }
+}
+
+impl std::str::FromStr for Visual {
+    type Err = String;
+
+    /// `none`, `plain`, `term` or `term:<ms>`, and `dump:`, `gif:` or `cast:<dir>`
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.split_once(':') {
+            None if s == "none" => Ok(Visual::None),
+            None if s == "plain" => Ok(Visual::Plain),
+            None if s == "term" => Ok(Visual::Terminal(DEFAULT_DELAY)),
+            Some(("term", ms)) => match ms.parse::<u64>() {
+                Ok(ms) => Ok(Visual::Terminal(Duration::from_millis(ms))),
+                _ => Err(format!("the frame delay should be milliseconds, not {ms}")),
+            },
+            Some(("dump", dir)) if !dir.is_empty() => Ok(Visual::Dump(Path::new(dir).into())),
+            Some(("gif", dir)) if !dir.is_empty() => Ok(Visual::Gif(dir.into(), Pace::default())),
+            Some(("cast", dir)) if !dir.is_empty() => {
+                Ok(Visual::Cast(dir.into(), Pace::default()))
+            }
+            None if matches!(s, "dump" | "gif" | "cast") => {
+                Err(format!("{s} needs a directory, as in {s}:<dir>"))
+            }
+            _ => Err(format!(
+                "unknown visualiser {s}, expected none, plain, term[:ms], dump:<dir>, gif:<dir> or cast:<dir>"
+            )),
+        }
+    }
+}
+
+thread_local! {
+    static SHOWN: RefCell<Option<Box<dyn Visualiser>>> = const { RefCell::new(None) };
+}
+
+/// Puts the visualiser in place on this thread while `f` runs, finishing it afterwards.
+/// The one in place before is put back, even if `f` panics.
+pub fn showing<R>(visualiser: Box<dyn Visualiser>, f: impl FnOnce() -> AResult<R>) -> AResult<R> {
+    struct Restore(Option<Box<dyn Visualiser>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SHOWN.with(|shown| *shown.borrow_mut() = self.0.take());
+        }
+    }
+
+    let restore = Restore(SHOWN.with(|shown| shown.borrow_mut().replace(visualiser)));
+    let result = f();
+    let finished = with_shown(|visualiser| visualiser.finish());
+    drop(restore);
+    let result = result?;
+    finished.map(|_| result)
+}
+
+/// Whether anything on this thread will show a frame, for solvers with work to do
+/// beyond drawing one
+pub fn is_shown() -> bool {
+    SHOWN.with(|shown| shown.borrow().as_ref().is_some_and(|v| v.is_shown()))
+}
+
+/// Pushes the frame `draw` makes, only calling it when the frame will be shown
+pub fn frame(draw: impl FnOnce() -> Frame) -> AResult<()> {
+    if !is_shown() {
+        return Ok(());
+    }
+    let frame = draw();
+    with_shown(|visualiser| visualiser.frame(&frame))
+}
+
+/// Pushes the event `describe` gives, only calling it when the event will be shown
+pub fn event(describe: impl FnOnce() -> String) -> AResult<()> {
+    if !is_shown() {
+        return Ok(());
+    }
+    let text = describe();
+    with_shown(|visualiser| visualiser.event(&text))
+}
+
+fn with_shown(f: impl FnOnce(&mut dyn Visualiser) -> AResult<()>) -> AResult<()> {
+    SHOWN.with(|shown| match shown.borrow_mut().as_mut() {
+        Some(visualiser) => f(visualiser.as_mut()),
+        None => Ok(()),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn two_by_two(caption: &str) -> Frame {
+        Frame::from_iter([
+            vec![Cell::new('#', Style::Wall), Cell::plain('.')],
+            vec![Cell::new('S', Style::Marker), Cell::new('o', Style::Path)],
+        ])
+        .with_caption(caption)
+    }
+
+    #[test]
+    fn draws_frames_plain_and_coloured() {
+        let frame = two_by_two("step 1");
+        assert_eq!("#.\nSo\nstep 1\n", frame.text());
+        assert_eq!(
+            "\x1b[40;90m#\x1b[0m.\n\x1b[30;103mS\x1b[0m\x1b[42;30mo\x1b[0m\nstep 1\n",
+            frame.coloured()
+        );
+        assert_eq!("#.\nSo\n", two_by_two("").text());
+    }
+
+    #[test]
+    fn pushes_only_to_the_visualiser_in_place() {
+        struct Shared(std::rc::Rc<RefCell<Vec<u8>>>);
+        impl Write for Shared {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.borrow_mut().write(buf)
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let drawn = std::cell::Cell::new(0);
+        let push = || {
+            frame(|| {
+                drawn.set(drawn.get() + 1);
+                two_by_two("")
+            })?;
+            event(|| "found one".to_string())
+        };
+
+        push().unwrap();
+        showing(Box::new(Hidden), push).unwrap();
+        assert_eq!(0, drawn.get());
+
+        let out = std::rc::Rc::new(RefCell::new(Vec::new()));
+        showing(Box::new(PlainText::new(Shared(out.clone()))), || {
+            assert!(is_shown());
+            push()
+        })
+        .unwrap();
+        assert_eq!(1, drawn.get());
+        assert!(!is_shown());
+        assert_eq!(
+            "#.\nSo\n\nfound one\n",
+            String::from_utf8_lossy(&out.borrow())
+        );
+    }
+
+    #[test]
+    fn dumps_frames_to_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-visual-{}", std::process::id()));
+        let visual: Visual = format!("dump:{}", dir.display()).parse().unwrap();
+        showing(visual.visualiser("day6-1").unwrap(), || {
+            frame(|| two_by_two("first"))?;
+            event(|| "loop".to_string())?;
+            frame(|| two_by_two("second"))
+        })
+        .unwrap();
+
+        let case = dir.join("day6-1");
+        let read = |name: &str| std::fs::read_to_string(case.join(name)).unwrap();
+        assert_eq!("#.\nSo\nfirst\n", read("frame-00001.txt"));
+        assert_eq!("#.\nSo\nsecond\n", read("frame-00002.txt"));
+        assert_eq!("00001 loop\n", read("events.txt"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn parses_the_choice_of_visualiser() {
+        assert_eq!(Ok(Visual::None), "none".parse());
+        assert_eq!(Ok(Visual::Plain), "plain".parse());
+        assert_eq!(Ok(Visual::Terminal(DEFAULT_DELAY)), "term".parse());
+        assert_eq!(
+            Ok(Visual::Terminal(Duration::from_millis(20))),
+            "term:20".parse()
+        );
+        assert_eq!(Ok(Visual::Dump("frames".into())), "dump:frames".parse());
+        assert_eq!(
+            Ok(Visual::Gif("shared".into(), Pace::default())),
+            "gif:shared".parse()
+        );
+        let pace = Pace { fps: 30, every: 5 };
+        assert_eq!(
+            Ok(Visual::Cast("shared".into(), pace)),
+            "cast:shared".parse::<Visual>().unwrap().paced(pace)
+        );
+        assert!(Visual::Plain.paced(pace).is_err());
+        assert!("dump".parse::<Visual>().is_err());
+        assert!("gif".parse::<Visual>().is_err());
+        assert!("term:fast".parse::<Visual>().is_err());
+        assert!("svg:maze".parse::<Visual>().is_err());
+    }
+}
@@ -1,15 +1,37 @@
+use aoc_core::error::parse_at;
+use aoc_core::files::split_words;
+use aoc_core::misc::AResult;
+use aoc_core::solution::Solution;
 use std::collections::HashMap;
 
-pub fn run_on_string(input: &str, part: u8) -> Result<i64, String> {
-    let blink_count = if part == 1 { 25 } else { 75 };
+pub struct Solver;
 
+impl Solution for Solver {
+    type Input = Vec<Stone>;
+    type Answer = Count;
+
+    fn parse(input: &str) -> AResult<Vec<Stone>> {
+        split_words(input)
+            .map(|v| parse_at::<Stone>(input, v))
+            .collect()
+    }
+
+    fn part1(stones: &Vec<Stone>) -> AResult<Count> {
+        Ok(blink(stones, 25))
+    }
+
+    fn part2(stones: &Vec<Stone>) -> AResult<Count> {
+        Ok(blink(stones, 75))
+    }
+}
+
+fn blink(stones: &[Stone], blink_count: Iterations) -> Count {
     let mut cache = HashMap::new();
 
-    input
-        .split(' ')
-        .map(parse_to::<Stone>)
-        .map_ok(|stone| simulate_blinks(stone, blink_count, &mut cache))
-        .sum_ok()
+    stones
+        .iter()
+        .map(|&stone| simulate_blinks(stone, blink_count, &mut cache))
+        .sum()
 }
 
 fn simulate_blinks(
@@ -46,8 +68,8 @@ fn simulate_blinks(
 
 fn split(value: Stone, digit_count: u32) -> (Stone, Stone) {
     let half_digit_count = digit_count / 2;
-    let new_left = value / (10u64).pow(half_digit_count as u32);
-    let new_right = value - (new_left * (10u64).pow(half_digit_count as u32));
+    let new_left = value / (10u64).pow(half_digit_count);
+    let new_right = value - (new_left * (10u64).pow(half_digit_count));
     (new_left, new_right)
 }
 
@@ -55,13 +77,6 @@ type Stone = u64;
 type Count = i64;
 type Iterations = u32;
 
-fn parse_to<T>(v: &str) -> Result<T, String>
-where
-    T: std::str::FromStr,
-{
-    v.parse::<T>().map_err(|_| format!("couldn't parse {v}"))
-}
-
 trait TupleFunctions<T> {
     fn apply<F, TOut>(self, f: &mut F) -> (TOut, TOut)
     where
@@ -90,45 +105,6 @@ impl<T> TupleFunctions<T> for (T, T) {
     }
 }
 
-impl<I, T, E> IteratorHelpers<T, E> for I where I: Iterator<Item = Result<T, E>> {}
-
-trait IteratorHelpers<TInput, E>: Iterator<Item = Result<TInput, E>> + Sized {
-    fn map_ok<TOutput, FMutate>(self, f: FMutate) -> MapOkIterator<Self, FMutate>
-    where
-        FMutate: FnMut(TInput) -> TOutput,
-    {
-        MapOkIterator {
-            iter: self,
-            func: f,
-        }
-    }
-
-    fn sum_ok(&mut self) -> Result<TInput, E>
-    where
-        TInput: std::ops::Add<Output = TInput>,
-        TInput: Default,
-    {
-        self.try_fold(TInput::default(), |acc, res| Ok(acc + res?))
-    }
-}
-
-struct MapOkIterator<I, F> {
-    iter: I,
-    func: F,
-}
-
-impl<I, FMutate, TInput, TOutput, E> Iterator for MapOkIterator<I, FMutate>
-where
-    I: Iterator<Item = Result<TInput, E>>,
-    FMutate: FnMut(TInput) -> TOutput,
-{
-    type Item = Result<TOutput, E>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|result| Ok((self.func)(result?)))
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -148,9 +124,9 @@ mod test {
         assert_eq!(34, left);
         assert_eq!(56, right);
     }
-}
 
-// public modules for bencher
-pub mod files;
-pub mod input_finder;
-pub mod misc;
+    #[test]
+    fn reads_stones_saved_with_a_newline() {
+        assert_eq!(vec![125, 17], Solver::parse("125 17\r\n").unwrap());
+    }
+}
@@ -1,15 +1,48 @@
-pub fn run_on_string(input: &str, part: u8, _is_benchmark: bool) -> Result<i64, String> {
-    let prize_offset = if part == 1 { 0f64 } else { 10000000000000f64 };
+use aoc_core::error::{parse_at, ParseError};
+use aoc_core::files::{split_lines, split_sections};
+use aoc_core::misc::AResult;
+use aoc_core::solution::Solution;
 
-    Ok(input
-        .split("\n\n")
-        .map(|machine_lines| {
-            let mut lines = machine_lines.lines();
+pub struct Solver;
 
-            let a = xy_extract(&mut lines, "Button A: X+", ", Y+");
-            let b = xy_extract(&mut lines, "Button B: X+", ", Y+");
-            let prize = xy_extract(&mut lines, "Prize: X=", ", Y=");
+impl Solution for Solver {
+    type Input = Vec<Machine>;
+    type Answer = i64;
 
+    fn parse(input: &str) -> AResult<Vec<Machine>> {
+        split_sections(input)
+            .map(|machine_lines| {
+                let mut lines = split_lines(machine_lines);
+
+                Ok(Machine {
+                    a: xy_extract(input, machine_lines, &mut lines, "Button A: X+", ", Y+")?,
+                    b: xy_extract(input, machine_lines, &mut lines, "Button B: X+", ", Y+")?,
+                    prize: xy_extract(input, machine_lines, &mut lines, "Prize: X=", ", Y=")?,
+                })
+            })
+            .collect()
+    }
+
+    fn part1(machines: &Vec<Machine>) -> AResult<i64> {
+        Ok(count_tokens(machines, 0f64))
+    }
+
+    fn part2(machines: &Vec<Machine>) -> AResult<i64> {
+        Ok(count_tokens(machines, 10000000000000f64))
+    }
+}
+
+#[derive(Debug)]
+pub struct Machine {
+    a: XY,
+    b: XY,
+    prize: XY,
+}
+
+fn count_tokens(machines: &[Machine], prize_offset: f64) -> i64 {
+    machines
+        .iter()
+        .map(|Machine { a, b, prize }| {
             let p = XY {
                 x: prize.x + prize_offset,
                 y: prize.y + prize_offset,
@@ -24,7 +57,7 @@ pub fn run_on_string(input: &str, part: u8, _is_benchmark: bool) -> Result<i64,
 
             3 * (presses_a as i64) + presses_b as i64
         })
-        .sum())
+        .sum()
 }
 
 #[derive(Debug)]
@@ -33,25 +66,31 @@ struct XY {
     y: f64,
 }
 
-fn xy_extract<'a>(lines: &mut impl Iterator<Item = &'a str>, prefix: &str, sep: &str) -> XY {
-    lines
-        .next()
-        .map(|line| {
-            let parts = line
-                .trim_start_matches(prefix)
-                .split(sep)
-                .map(|i| i.parse::<i64>().unwrap())
-                .collect::<Vec<i64>>();
-
-            XY {
-                x: parts[0] as f64,
-                y: parts[1] as f64,
-            }
-        })
-        .unwrap()
-}
+fn xy_extract<'a>(
+    input: &str,
+    machine: &str,
+    lines: &mut impl Iterator<Item = &'a str>,
+    prefix: &str,
+    sep: &str,
+) -> AResult<XY> {
+    let line = lines.next().ok_or_else(|| {
+        ParseError::at(
+            input,
+            &machine[machine.len()..],
+            format!("expected a line starting '{prefix}'"),
+        )
+    })?;
 
-// public modules for bencher
-pub mod files;
-pub mod input_finder;
-pub mod misc;
+    let values = line.strip_prefix(prefix).ok_or_else(|| {
+        ParseError::at(input, line, format!("expected a line starting '{prefix}'"))
+    })?;
+
+    let (x, y) = values.split_once(sep).ok_or_else(|| {
+        ParseError::at(input, values, format!("expected '{sep}' between X and Y"))
+    })?;
+
+    Ok(XY {
+        x: parse_at::<i64>(input, x)? as f64,
+        y: parse_at::<i64>(input, y)? as f64,
+    })
+}
@@ -1,2 +1,4 @@
-// benchmarks are generated using the build script and ../src_templates/benchmark?.rst
-include!(concat!(env!("OUT_DIR"), "/benchmarks.rs"));
+// benchmarks for each real case in the day's config, see aoc_core::benchmarks
+fn main() -> std::process::ExitCode {
+    aoc_core::benchmarks::run::<day16::Solver>(env!("CARGO_PKG_NAME"))
+}
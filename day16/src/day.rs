@@ -1,21 +1,83 @@
 use crate::{maze::Maze, maze_graph::MazeGraph};
+use aoc_core::misc::AResult;
+use aoc_core::solution::Solution;
+use aoc_core::visual;
+use std::path::Path;
 
-pub fn run_on_string(input: &str, part: u8, is_real: bool) -> Result<i64, String> {
-    let maze = Maze::new_from_string(input);
-    if !is_real {
-        //maze.print();
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Maze;
+    type Answer = i64;
+
+    fn parse(input: &str) -> AResult<Maze> {
+        Maze::new_from_string(input)
     }
 
-    let mut graph = MazeGraph::new(&maze);
-    graph.identify_shortest_connections(maze.start());
-    let result = graph.mark_shortest_path(maze.start(), maze.end());
-    if !is_real {
-        //graph.print(maze.start(), maze.end());
-        graph.print_tight(maze.start(), maze.end());
+    fn part1(maze: &Maze) -> AResult<i64> {
+        Ok(shortest_path(maze)?.1)
     }
 
-    Ok(match part {
-        1 => result,
-        _ => graph.count_seats(),
-    })
+    fn part2(maze: &Maze) -> AResult<i64> {
+        Ok(shortest_path(maze)?.0.count_seats())
+    }
+}
+
+/// Solves the maze and saves it as an SVG picture with the best paths through it
+pub fn export_svg(maze: &Maze, path: impl AsRef<Path>) -> AResult<()> {
+    let (graph, _) = shortest_path(maze)?;
+    graph.svg(maze.start(), maze.end()).save(path)
+}
+
+fn shortest_path(maze: &Maze) -> AResult<(MazeGraph, i64)> {
+    visual::frame(|| maze.frame())?;
+    let mut graph = MazeGraph::new(maze);
+    graph.identify_shortest_connections(maze.start());
+    let score = graph.mark_shortest_path(maze.start(), maze.end());
+    visual::frame(|| {
+        graph
+            .frame(maze.start(), maze.end())
+            .with_caption(format!("score: {score}"))
+    })?;
+    Ok((graph, score))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn exports_the_solved_maze() {
+        let maze = Maze::new_from_string(
+            "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############",
+        )
+        .unwrap();
+        let path = std::env::temp_dir().join(format!("day16-{}.svg", std::process::id()));
+        export_svg(&maze, &path).unwrap();
+        let svg = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        for layer in ["walls", "primary-path", "secondary-path", "start", "end"] {
+            assert!(
+                svg.contains(&format!("<g id=\"{layer}\"")),
+                "{layer} in {svg}"
+            );
+        }
+        assert!(svg.contains("<g id=\"start\" fill=\"#ffdd33\">\n<rect x=\"10\" y=\"130\" "));
+    }
 }
@@ -1,4 +1,5 @@
 use crate::xy::XY;
+use aoc_core::svg::{Layer, Mark, Svg};
 
 pub type GridRow<T> = Vec<T>;
 
@@ -9,6 +10,12 @@ pub struct Grid<T> {
     height: i64,
 }
 
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> Grid<T> {
     pub fn new() -> Self {
         Self {
@@ -22,8 +29,8 @@ impl<T> Grid<T> {
         let height = cells.len();
         let width = if height > 0 { cells[0].len() } else { 0 };
 
-        for row in 0..height {
-            assert!(cells[row].len() == width)
+        for row in &cells {
+            assert!(row.len() == width)
         }
 
         Self {
@@ -76,4 +83,12 @@ impl<T> Grid<T> {
     pub fn at_rc_mut(&mut self, row: usize, col: usize) -> &mut T {
         &mut self.cells[row][col]
     }
+
+    /// A picture of the grid, each cell `layer` picks out drawn on that layer, for
+    /// more layers to be added over it
+    pub fn svg(&self, layer: impl Fn(&T) -> Option<Layer>) -> Svg {
+        Svg::from_grid(self.row_count(), self.col_count(), |(row, col)| {
+            layer(&self.cells[row][col]).map(Mark::On)
+        })
+    }
 }
@@ -1,54 +1,27 @@
-mod config;
-mod files;
+use aoc_core::runner;
+use std::process::ExitCode;
 
 include!(concat!(env!("OUT_DIR"), "/lib_alias.rs"));
 
-fn main() {
+fn main() -> ExitCode {
     // ASSUMPTION
     // there is a folder called <input> in the root of your Cargo project
     // it contains input and configuration files
     // For each day there is a config file
-    // <day1.config>
-    // This file specifies the [part, mode, input filename, expected result] one per line
-    // e.g. 1,t,day1-test.txt,17
+    // <day1.toml>
+    // This file holds one [[case]] per input, naming its part, mode, input file and expected result
+    // e.g. part = 1, mode = "test", input = "day1-test.txt", expected = 17
     // would say that part <1> <Test> input in file <day1-test.txt> expects a result of <17>
-    // to disable running a line prefix it with //s
+    // see aoc_core::config for the full format and the older <day1.config> it replaces
 
     // NOTE: the expected result is only used in cargo test
 
     let day = std::env::var("CARGO_PKG_NAME").unwrap();
-
-    let parts = config::read_config(&day).expect("a config file in the form <dayX.config>");
-
-    if parts.len() == 0 {
-        println!("no active lines found in the config file");
-    }
-
-    for part in &parts {
-        println!(
-            "Running day {} part {} using {} data",
-            day, part.part, part.mode
-        );
-        match generate_result(&part) {
-            Ok(result) => println!("result: {result}",),
-            Err(e) => println!("{} {}", day, e),
+    match runner::run_day::<lib::Solver>(&day) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{day} :: {e}");
+            ExitCode::FAILURE
         }
     }
 }
-
-fn generate_result(part: &config::Part) -> Result<i64, String> {
-    use std::time::Instant;
-    let now = Instant::now();
-    let result = run(&part.filename, part.part, part.mode == config::Mode::Real)
-        .map_err(|e| format!("Failed for part {} <{}> :: {}", part.part, part.filename, e))?;
-    let elapsed = now.elapsed();
-    println!("Elapsed: {:.2?}", elapsed);
-    Ok(result)
-}
-
-pub fn run(filename: &str, part: u8, bench: bool) -> Result<i64, String> {
-    let input = files::load_full_input_as_string(filename)?;
-    lib::run_on_string(&input, part, bench)
-}
-
-include!(concat!(env!("OUT_DIR"), "/tests.rs"));
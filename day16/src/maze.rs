@@ -1,5 +1,9 @@
 use crate::grid::{Grid, GridRow};
 use crate::xy::XY;
+use aoc_core::error::ParseError;
+use aoc_core::files::split_lines;
+use aoc_core::misc::AResult;
+use aoc_core::visual::{Cell, Frame, Style};
 
 #[derive(Debug)]
 pub struct Maze {
@@ -15,7 +19,7 @@ pub enum Kind {
 }
 
 impl Maze {
-    pub fn new_from_string(input: &str) -> Self {
+    pub fn new_from_string(input: &str) -> AResult<Self> {
         read(input)
     }
 
@@ -46,54 +50,67 @@ impl Maze {
         self.map.at(pos)
     }
 
-    pub fn print(&self) {
+    pub fn frame(&self) -> Frame {
+        let mut frame = Frame::new();
         for row in 0..self.row_count() {
-            for col in 0..self.col_count() {
-                match XY::from_rc(row, col) {
-                    p if p == self.start => print!("S"),
-                    p if p == self.end => print!("E"),
+            frame.push_row(
+                (0..self.col_count()).map(|col| match XY::from_rc(row, col) {
+                    p if p == self.start => Cell::new('S', Style::Marker),
+                    p if p == self.end => Cell::new('E', Style::Marker),
                     p => match *self.at(p) {
-                        Kind::Wall => print!("█"),
-                        Kind::Floor => print!("."),
+                        Kind::Wall => Cell::new('█', Style::Wall),
+                        Kind::Floor => Cell::plain('.'),
                     },
-                }
-            }
-            println!()
+                }),
+            );
         }
-        println!()
+        frame
     }
 }
 
-fn read(input: &str) -> Maze {
+fn read(input: &str) -> AResult<Maze> {
     let mut start: Option<XY> = None;
     let mut end: Option<XY> = None;
+    let mut width: Option<usize> = None;
 
-    let map = input
-        .lines()
+    let map = split_lines(input)
         .enumerate()
         .map(|(row, line)| {
-            line.chars()
-                .enumerate()
+            if *width.get_or_insert(line.len()) != line.len() {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "every row of the maze must be the same width",
+                )
+                .into());
+            }
+
+            line.char_indices()
                 .map(|(col, c)| match c {
-                    '#' => Kind::Wall,
-                    '.' => Kind::Floor,
+                    '#' => Ok(Kind::Wall),
+                    '.' => Ok(Kind::Floor),
                     'S' => {
                         start = Some(XY::from_rc(row, col));
-                        Kind::Floor
+                        Ok(Kind::Floor)
                     }
                     'E' => {
                         end = Some(XY::from_rc(row, col));
-                        Kind::Floor
+                        Ok(Kind::Floor)
                     }
-                    _ => panic!("unknown input"),
+                    _ => Err(ParseError::at(
+                        input,
+                        &line[col..col + c.len_utf8()],
+                        format!("unknown input '{c}'"),
+                    )
+                    .into()),
                 })
-                .collect::<GridRow<Kind>>()
+                .collect::<AResult<GridRow<Kind>>>()
         })
-        .collect::<Vec<GridRow<Kind>>>();
+        .collect::<AResult<Vec<GridRow<Kind>>>>()?;
 
-    Maze {
+    Ok(Maze {
         map: Grid::from_cells(map),
-        start: start.expect("should have a starting position"),
-        end: end.expect("should have a starting position"),
-    }
+        start: start.ok_or_else(|| ParseError::at_end(input, "should have a starting position"))?,
+        end: end.ok_or_else(|| ParseError::at_end(input, "should have an ending position"))?,
+    })
 }
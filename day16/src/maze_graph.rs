@@ -3,6 +3,8 @@ use crate::grid::{Grid, GridRow};
 use crate::maze::{Kind, Maze};
 use crate::xy::XY;
 
+use aoc_core::svg::{Layer, Svg};
+use aoc_core::visual::{self, Frame, Style};
 use priority_queue::PriorityQueue;
 use std::cmp::Reverse;
 use std::collections::HashMap;
@@ -138,11 +140,9 @@ fn populate_connections(map: &mut MazeMap) -> MinPriorityQueue<RouteId> {
             }
 
             let get_cell_id_to_the = |direction: Direction| -> Option<CellId> {
-                if let Some(cell) = map.at(cell_xy.offset_with(direction.as_offset())).as_ref() {
-                    Some(cell.cell_id)
-                } else {
-                    None
-                }
+                map.at(cell_xy.offset_with(direction.as_offset()))
+                    .as_ref()
+                    .map(|cell| cell.cell_id)
             };
 
             let cardinal_targets = [
@@ -276,13 +276,11 @@ impl MazeGraph {
                     let mut best_source_score = i64::MAX;
 
                     if let Some(route) = &self.route_from(route) {
-                        for source in route.sources {
-                            if let Some(source) = source {
-                                if let Some(route) = self.route_from(source) {
-                                    if route.node_weight < best_source_score {
-                                        best_source_score = route.node_weight;
-                                        best_source = Some(route.id);
-                                    }
+                        for source in route.sources.into_iter().flatten() {
+                            if let Some(route) = self.route_from(source) {
+                                if route.node_weight < best_source_score {
+                                    best_source_score = route.node_weight;
+                                    best_source = Some(route.id);
                                 }
                             }
                         }
@@ -337,58 +335,39 @@ impl MazeGraph {
                 let mut best_sourcees: Vec<RouteId> = Vec::new();
 
                 if let Some(current_node) = self.route_from(route) {
-                    for opt_source_node_id in current_node.sources {
-                        if let Some(source_node_id) = opt_source_node_id {
-                            if let Some(source_node) = self.route_from(source_node_id) {
-                                for opt_connection in &source_node.destinations {
-                                    if let Some(connection) = opt_connection {
-                                        if connection.target == route
-                                            && source_node.node_weight + connection.weight
-                                                == current_weight
+                    for source_node_id in current_node.sources.into_iter().flatten() {
+                        let Some(source_node) = self.route_from(source_node_id) else {
+                            continue;
+                        };
+                        for connection in source_node.destinations.iter().flatten() {
+                            if connection.target == route
+                                && source_node.node_weight + connection.weight == current_weight
+                            {
+                                // source_node is linked to current_node via connection and has a suitable weighting
+
+                                if connection.turn == Turn::Forward {
+                                    // it's a forward link, implying that the source node was in a different cell
+                                    best_sourcees.push(source_node_id);
+                                } else {
+                                    // it's a turn, so it is still within the same cell.
+                                    // that's fine but only if the next hop is a forward
+
+                                    for hop_node_id in source_node.sources.into_iter().flatten() {
+                                        let Some(hop_node) = self.route_from(hop_node_id) else {
+                                            continue;
+                                        };
+                                        for hop_connection in hop_node.destinations.iter().flatten()
                                         {
-                                            // source_node is linked to current_node via connection and has a suitable weighting
-
-                                            if connection.turn == Turn::Forward {
-                                                // it's a forward link, implying that the source node was in a different cell
-                                                best_sourcees.push(source_node_id);
-                                            } else {
-                                                // it's a turn, so it is still within the same cell.
-                                                // that's fine but only if the next hop is a forward
-
-                                                for opt_hop_node_id in source_node.sources {
-                                                    if let Some(hop_node_id) = opt_hop_node_id {
-                                                        if let Some(hop_node) =
-                                                            self.route_from(hop_node_id)
-                                                        {
-                                                            for opt_hop_connection in
-                                                                &hop_node.destinations
-                                                            {
-                                                                if let Some(hop_connection) =
-                                                                    opt_hop_connection
-                                                                {
-                                                                    if hop_connection.target
-                                                                        == source_node_id
-                                                                        && hop_node.node_weight
-                                                                            + hop_connection.weight
-                                                                            == source_node
-                                                                                .node_weight
-                                                                    {
-                                                                        // hop_node is linked to source_node via hop_connection
-
-                                                                        if hop_connection.turn
-                                                                            == Turn::Forward
-                                                                        {
-                                                                            // it's a forward link, implying that the node was in a different cell
-                                                                            // add the source node as the next valid destination
-                                                                            best_sourcees.push(
-                                                                                source_node_id,
-                                                                            );
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
+                                            if hop_connection.target == source_node_id
+                                                && hop_node.node_weight + hop_connection.weight
+                                                    == source_node.node_weight
+                                            {
+                                                // hop_node is linked to source_node via hop_connection
+
+                                                if hop_connection.turn == Turn::Forward {
+                                                    // it's a forward link, implying that the node was in a different cell
+                                                    // add the source node as the next valid destination
+                                                    best_sourcees.push(source_node_id);
                                                 }
                                             }
                                         }
@@ -464,22 +443,37 @@ impl MazeGraph {
         self.nodes.change_priority(&id, Reverse(weight));
     }
 
-    pub fn print_tight(&self, start: XY, end: XY) {
+    /// The maze with the best paths through it picked out
+    pub fn frame(&self, start: XY, end: XY) -> Frame {
+        let mut frame = Frame::new();
         for row in 0..self.map.row_count() {
-            for col in 0..self.map.col_count() {
+            frame.push_row((0..self.map.col_count()).map(|col| {
                 match &self.map.at(XY::from_rc(row, col)) {
-                    Some(cell) if cell.cell_id.0 == start => print!("\x1b[30;103mS\x1b[0m"),
-                    Some(cell) if cell.cell_id.0 == end => print!("\x1b[30;103mE\x1b[0m"),
-                    Some(cell) if cell.is_on_primary_path => print!("\x1b[42;30m \x1b[0m"),
-                    Some(cell) if cell.is_on_secondary_path => print!("\x1b[46;30m \x1b[0m"),
-                    Some(_) => print!(" "),
-                    None => print!("\x1b[40;90m█\x1b[0m"),
-                    //None => print!("█"),
-                };
-            }
-            println!()
+                    Some(cell) if cell.cell_id.0 == start => visual::Cell::new('S', Style::Marker),
+                    Some(cell) if cell.cell_id.0 == end => visual::Cell::new('E', Style::Marker),
+                    Some(cell) if cell.is_on_primary_path => visual::Cell::new('O', Style::Path),
+                    Some(cell) if cell.is_on_secondary_path => {
+                        visual::Cell::new('o', Style::SidePath)
+                    }
+                    Some(_) => visual::Cell::plain(' '),
+                    None => visual::Cell::new('█', Style::Wall),
+                }
+            }));
         }
-        println!()
+        frame
+    }
+
+    /// The maze as a picture, the best paths through it on their own layers
+    pub fn svg(&self, start: XY, end: XY) -> Svg {
+        self.map
+            .svg(|cell| match cell {
+                None => Some(Layer::Walls),
+                Some(cell) if cell.is_on_primary_path => Some(Layer::PrimaryPath),
+                Some(cell) if cell.is_on_secondary_path => Some(Layer::SecondaryPath),
+                Some(_) => None,
+            })
+            .with(Layer::Start, [(start.row(), start.col())])
+            .with(Layer::End, [(end.row(), end.col())])
     }
 
     pub fn print(&self, start: XY, end: XY) {
@@ -14,10 +14,10 @@ impl XY {
     }
 
     pub fn x(&self) -> i64 {
-        self.y
+        self.x
     }
     pub fn y(&self) -> i64 {
-        self.x
+        self.y
     }
 
     pub fn row(&self) -> usize {
@@ -1,7 +1,7 @@
 #[derive(Debug, PartialEq)]
 pub enum Operation {
-    MUL(i32, i32),
-    COND(bool),
+    Mul(i32, i32),
+    Cond(bool),
 }
 
 #[derive(Debug)]
@@ -20,12 +20,12 @@ impl ElvishCalculator {
 
     pub fn enter(&mut self, op: Operation) {
         match op {
-            Operation::MUL(a1, a2) => {
+            Operation::Mul(a1, a2) => {
                 if self.enabled {
                     self.result += a1 * a2
                 }
             }
-            Operation::COND(toggle) => self.enabled = toggle,
+            Operation::Cond(toggle) => self.enabled = toggle,
         };
     }
 
@@ -0,0 +1,109 @@
+// Computer Issues - Mull it over
+
+mod calculator;
+mod parser;
+
+use aoc_core::files;
+use aoc_core::misc::AResult;
+use aoc_core::solution::Solution;
+use calculator::ElvishCalculator;
+use parser::ElvishMachineLanguageParser;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    /// the corrupted memory, instructions are picked out of it as it is read
+    type Input = String;
+    type Answer = i32;
+
+    fn parse(input: &str) -> AResult<String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(memory: &String) -> AResult<i32> {
+        calculate(1, |machine| {
+            machine.feed(memory.chars());
+            Ok(())
+        })
+    }
+
+    fn part2(memory: &String) -> AResult<i32> {
+        calculate(2, |machine| {
+            machine.feed(memory.chars());
+            Ok(())
+        })
+    }
+}
+
+/// The input is streamed through the parser this many bytes at a time
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// As the solver for both parts, streaming the input through a parser for each in the
+/// one pass, so it needn't fit in memory and stdin only needs reading once
+pub fn calculate_input(filename: &str) -> AResult<(i32, i32)> {
+    calculate_chunks(files::Chunks::new(
+        filename,
+        files::open_input(filename)?,
+        CHUNK_SIZE,
+    ))
+}
+
+fn calculate_chunks(mut chunks: files::Chunks) -> AResult<(i32, i32)> {
+    let (mut calc1, mut calc2) = (ElvishCalculator::new(), ElvishCalculator::new());
+    let mut part1 = ElvishMachineLanguageParser::new(&mut calc1, false);
+    let mut part2 = ElvishMachineLanguageParser::new(&mut calc2, true);
+
+    // bytes rather than chars, anything outside ascii can't be part of an instruction anyway
+    while let Some(chunk) = chunks.next_chunk()? {
+        part1.feed(chunk.iter().map(|&b| char::from(b)));
+        part2.feed(chunk.iter().map(|&b| char::from(b)));
+    }
+    part1.finish();
+    part2.finish();
+
+    Ok((calc1.result(), calc2.result()))
+}
+
+fn calculate(
+    part: u8,
+    read: impl FnOnce(&mut ElvishMachineLanguageParser) -> AResult<()>,
+) -> AResult<i32> {
+    let mut calc = ElvishCalculator::new();
+
+    let mut machine = ElvishMachineLanguageParser::new(&mut calc, part == 2);
+    read(&mut machine)?;
+    machine.finish();
+
+    Ok(calc.result())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLES: [&str; 2] = [
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))",
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+    ];
+
+    #[test]
+    fn streams_the_same_answers_as_the_solver() {
+        assert_eq!(161, Solver::solve(EXAMPLES[0], 1).unwrap());
+        assert_eq!(48, Solver::solve(EXAMPLES[1], 2).unwrap());
+
+        // the small sizes split the instructions across chunks
+        for example in EXAMPLES {
+            for size in [1, 3, CHUNK_SIZE] {
+                let reader = Box::new(std::io::Cursor::new(example.as_bytes().to_vec()));
+                assert_eq!(
+                    (
+                        Solver::solve(example, 1).unwrap(),
+                        Solver::solve(example, 2).unwrap()
+                    ),
+                    calculate_chunks(files::Chunks::new("example", reader, size)).unwrap(),
+                    "chunks of {size} from {example}"
+                );
+            }
+        }
+    }
+}
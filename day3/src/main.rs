@@ -1,68 +1,42 @@
-// Computer Issues - Mull it over
-
-mod calculator;
-mod parser;
-
-use std::io::Read;
-
-pub type FileReader = std::io::BufReader<std::fs::File>;
-pub type AResult<T> = Result<T, String>;
-
-fn main() {
-    println!(
-        "Part 1 Test result: {}",
-        run_calculations("day3-test.txt", 1).expect("a result")
-    );
-    println!(
-        "Part 2 Test result: {}",
-        run_calculations("day3-test2.txt", 2).expect("a result")
-    );
-
-    println!();
-
-    println!(
-        "Part 1 result: {}",
-        run_calculations("day3.txt", 1).expect("a result")
-    );
-    println!(
-        "Part 2 result: {}",
-        run_calculations("day3.txt", 2).expect("a result")
-    );
-}
-
-fn run_calculations(filename: &str, part: u8) -> AResult<i32> {
-    let mut calc = calculator::ElvishCalculator::new();
-
-    let mut machine = parser::ElvishMachineLanguageParser::new(&mut calc, part == 2);
-    machine.load_string(load_full_input_as_string(filename)?.as_str());
-
-    Ok(calc.result())
-}
-
-pub fn load_full_input_as_string(filename: &str) -> AResult<String> {
-    let mut file = open_file(filename)?;
-    let mut buffer = String::new();
-    buffer.reserve(4096);
-
-    let char_count = file
-        .read_to_string(&mut buffer)
-        .map_err(|e| format!("failed to read bytes {e:?}"))?;
-
-    if char_count == 0 {
-        return Err("no data found".to_string());
+use aoc_core::runner;
+use std::process::ExitCode;
+
+include!(concat!(env!("OUT_DIR"), "/lib_alias.rs"));
+
+fn main() -> ExitCode {
+    // ASSUMPTION
+    // there is a folder called <input> in the root of your Cargo project
+    // it contains input and configuration files
+    // For each day there is a config file
+    // <day1.toml>
+    // This file holds one [[case]] per input, naming its part, mode, input file and expected result
+    // e.g. part = 1, mode = "test", input = "day1-test.txt", expected = 17
+    // would say that part <1> <Test> input in file <day1-test.txt> expects a result of <17>
+    // see aoc_core::config for the full format and the older <day1.config> it replaces
+
+    // NOTE: the expected result is only used in cargo test
+
+    // an input named on the command line is streamed, for inputs too big to load
+    if let Some(filename) = std::env::args().nth(1) {
+        return match lib::calculate_input(&filename) {
+            Ok((part1, part2)) => {
+                println!("part 1 :: {part1}");
+                println!("part 2 :: {part2}");
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{filename} :: {e}");
+                ExitCode::FAILURE
+            }
+        };
     }
 
-    Ok(buffer)
-}
-
-pub fn open_file(project_relative_filename: &str) -> Result<FileReader, String> {
-    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
-        .join("input")
-        .join(project_relative_filename);
-
-    let file = std::fs::OpenOptions::new()
-        .read(true)
-        .open(path)
-        .map_err(|e| e.to_string())?;
-    Ok(std::io::BufReader::new(file))
+    let day = std::env::var("CARGO_PKG_NAME").unwrap();
+    match runner::run_day::<lib::Solver>(&day) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{day} :: {e}");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -3,7 +3,7 @@ use std::collections::VecDeque;
 
 #[derive(Debug, PartialEq)]
 enum TokenState {
-    BEGIN,
+    Begin,
     M,
     U,
     L,
@@ -40,6 +40,9 @@ pub struct ElvishMachineLanguageParser<'a> {
     partial_token: String,
     arg1: Option<i32>,
     arg2: Option<i32>,
+
+    /// chars seen since the last token, kept between feeds
+    read_behind_buffer: VecDeque<char>,
 }
 
 impl<'a> ElvishMachineLanguageParser<'a> {
@@ -47,44 +50,46 @@ impl<'a> ElvishMachineLanguageParser<'a> {
         Self {
             calculator,
             support_enabled_toggle,
-            state: TokenState::BEGIN,
+            state: TokenState::Begin,
             arg1: None,
             arg2: None,
             partial_token: String::new(),
+            read_behind_buffer: VecDeque::new(),
         }
     }
 
-    pub fn load_string(&mut self, input: &str) {
-        let mut char_source = input.chars();
-
-        let mut read_behind_buffer = VecDeque::<char>::new();
-        let mut i = 0;
+    /// Takes the next piece of the input, a token may carry on into the following one
+    pub fn feed(&mut self, mut char_source: impl Iterator<Item = char>) {
+        // whatever is left over from the last feed has been loaded already
+        let mut i = self.read_behind_buffer.len();
 
         loop {
-            if i >= read_behind_buffer.len() {
+            if i >= self.read_behind_buffer.len() {
                 match char_source.next() {
-                    Some(c) => read_behind_buffer.push_back(c),
+                    Some(c) => self.read_behind_buffer.push_back(c),
                     None => break,
                 }
             }
 
-            let c = read_behind_buffer[i];
+            let c = self.read_behind_buffer[i];
 
             match self.load(c) {
                 ParseResult::AcceptedChar => {
-                    i = i + 1;
+                    i += 1;
                 }
                 ParseResult::ConsumedUpTillNow => {
-                    read_behind_buffer = read_behind_buffer.split_off(i);
+                    self.read_behind_buffer = self.read_behind_buffer.split_off(i);
                     i = 0;
                 }
                 ParseResult::RejectedChar => {
-                    read_behind_buffer.remove(0);
+                    self.read_behind_buffer.remove(0);
                     i = 0;
                 }
             }
         }
+    }
 
+    pub fn finish(&mut self) {
         self.load(' '); // simulate eof
     }
 
@@ -92,23 +97,23 @@ impl<'a> ElvishMachineLanguageParser<'a> {
         self.arg1 = None;
         self.arg2 = None;
         self.partial_token.clear();
-        TokenState::BEGIN
+        TokenState::Begin
     }
 
     fn load(&mut self, c: char) -> ParseResult {
         self.state = match self.state {
-            TokenState::BEGIN if c == 'm' => TokenState::M,
-            TokenState::BEGIN if c == 'd' && self.support_enabled_toggle => TokenState::D,
-            TokenState::BEGIN => TokenState::BEGIN,
+            TokenState::Begin if c == 'm' => TokenState::M,
+            TokenState::Begin if c == 'd' && self.support_enabled_toggle => TokenState::D,
+            TokenState::Begin => TokenState::Begin,
 
             TokenState::M if c == 'u' => TokenState::U,
-            TokenState::M => TokenState::BEGIN,
+            TokenState::M => TokenState::Begin,
 
             TokenState::U if c == 'l' => TokenState::L,
-            TokenState::U => TokenState::BEGIN,
+            TokenState::U => TokenState::Begin,
 
             TokenState::L if c == '(' => TokenState::OpenParen,
-            TokenState::L => TokenState::BEGIN,
+            TokenState::L => TokenState::Begin,
 
             TokenState::OpenParen if c.is_ascii_digit() => {
                 self.state = TokenState::Arg1;
@@ -151,7 +156,7 @@ impl<'a> ElvishMachineLanguageParser<'a> {
                         self.arg2 = Some(num);
                         TokenState::CloseParen
                     }
-                    Err(_) => TokenState::BEGIN,
+                    Err(_) => TokenState::Begin,
                 };
                 self.partial_token.clear();
                 next_state
@@ -160,26 +165,26 @@ impl<'a> ElvishMachineLanguageParser<'a> {
             TokenState::Arg2 => self.reset_state(),
 
             TokenState::D if c == 'o' => TokenState::O,
-            TokenState::D => TokenState::BEGIN,
+            TokenState::D => TokenState::Begin,
 
             TokenState::O if c == 'n' => TokenState::N,
             TokenState::O if c == '(' => TokenState::OpenParenDo,
-            TokenState::O => TokenState::BEGIN,
+            TokenState::O => TokenState::Begin,
 
             TokenState::OpenParenDo if c == ')' => TokenState::CloseParenDo,
-            TokenState::OpenParenDo => TokenState::BEGIN,
+            TokenState::OpenParenDo => TokenState::Begin,
 
             TokenState::N if c == '\'' => TokenState::Appostrophe,
-            TokenState::N => TokenState::BEGIN,
+            TokenState::N => TokenState::Begin,
 
             TokenState::Appostrophe if c == 't' => TokenState::T,
-            TokenState::Appostrophe => TokenState::BEGIN,
+            TokenState::Appostrophe => TokenState::Begin,
 
             TokenState::T if c == '(' => TokenState::OpenParenDont,
-            TokenState::T => TokenState::BEGIN,
+            TokenState::T => TokenState::Begin,
 
             TokenState::OpenParenDont if c == ')' => TokenState::CloseParenDont,
-            TokenState::OpenParenDont => TokenState::BEGIN,
+            TokenState::OpenParenDont => TokenState::Begin,
 
             TokenState::CloseParen => panic!("failed to consume mul"),
             TokenState::CloseParenDo => panic!("failed to consume do"),
@@ -187,21 +192,21 @@ impl<'a> ElvishMachineLanguageParser<'a> {
         };
 
         match self.state {
-            TokenState::BEGIN => ParseResult::RejectedChar,
+            TokenState::Begin => ParseResult::RejectedChar,
             TokenState::CloseParen => {
                 self.calculator
-                    .enter(Operation::MUL(self.arg1.unwrap(), self.arg2.unwrap()));
+                    .enter(Operation::Mul(self.arg1.unwrap(), self.arg2.unwrap()));
                 self.state = self.reset_state();
                 ParseResult::ConsumedUpTillNow
             }
             TokenState::CloseParenDo => {
-                self.calculator.enter(Operation::COND(true));
-                self.state = TokenState::BEGIN;
+                self.calculator.enter(Operation::Cond(true));
+                self.state = TokenState::Begin;
                 ParseResult::ConsumedUpTillNow
             }
             TokenState::CloseParenDont => {
-                self.calculator.enter(Operation::COND(false));
-                self.state = TokenState::BEGIN;
+                self.calculator.enter(Operation::Cond(false));
+                self.state = TokenState::Begin;
                 ParseResult::ConsumedUpTillNow
             }
             _ => ParseResult::AcceptedChar,
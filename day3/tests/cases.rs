@@ -0,0 +1,4 @@
+// one test per case in the day's config, see aoc_core::harness
+fn main() -> std::process::ExitCode {
+    aoc_core::harness::run::<day3::Solver>(env!("CARGO_PKG_NAME"))
+}
@@ -0,0 +1,4 @@
+// benchmarks for each real case in the day's config, see aoc_core::benchmarks
+fn main() -> std::process::ExitCode {
+    aoc_core::benchmarks::run::<day4::Solver>(env!("CARGO_PKG_NAME"))
+}
@@ -0,0 +1,105 @@
+// Ceres Search
+
+use aoc_core::error::Error;
+use aoc_core::files::split_lines;
+use aoc_core::misc::AResult;
+use aoc_core::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Source;
+    type Answer = i32;
+
+    fn parse(input: &str) -> AResult<Source> {
+        Ok(Source::new(
+            split_lines(input).map(|r| r.chars().collect()).collect(),
+        ))
+    }
+
+    fn part1(source: &Source) -> AResult<i32> {
+        let mut count: Count = 0;
+
+        for col in 0..source.width {
+            for row in 0..source.height {
+                if source.rows[row][col] == 'X' {
+                    count += count_xmases(source, Point(col as i32, row as i32))
+                }
+            }
+        }
+
+        Ok(count)
+    }
+
+    fn part2(_: &Source) -> AResult<i32> {
+        Err(Error::solver("part 2 is not solved yet"))
+    }
+}
+
+fn count_xmases(source: &Source, p: Point) -> Count {
+    count_xmas(source, p, Offset(1, 0), Offset(2, 0), Offset(3, 0))
+        + count_xmas(source, p, Offset(0, 1), Offset(0, 2), Offset(0, 3))
+        + count_xmas(source, p, Offset(1, 1), Offset(2, 2), Offset(3, 3))
+        + count_xmas(source, p, Offset(1, -1), Offset(2, -2), Offset(3, -3))
+}
+
+fn count_xmas(source: &Source, p1: Point, o2: Offset, o3: Offset, o4: Offset) -> Count {
+    is_xmas(source, p1 + o2, p1 + o3, p1 + o4) + is_xmas(source, p1 + -o2, p1 + -o3, p1 + -o4)
+}
+
+fn is_xmas(source: &Source, p2: Point, p3: Point, p4: Point) -> Count {
+    (source.is(p2, 'M') && source.is(p3, 'A') && source.is(p4, 'S')) as Count
+}
+
+impl Source {
+    pub fn new(input: Vec<Vec<char>>) -> Self {
+        let height = input.len();
+        let width = if height > 0 { input[0].len() } else { 0 };
+
+        Self {
+            rows: input,
+            width,
+            height,
+        }
+    }
+
+    pub fn at(&self, p: Point) -> Option<char> {
+        if p.1 >= self.height as i32 || p.1 < 0 || p.0 >= self.width as i32 || p.0 < 0 {
+            None
+        } else {
+            Some(self.rows[p.1 as usize][p.0 as usize])
+        }
+    }
+
+    pub fn is(&self, p: Point, c: char) -> bool {
+        self.at(p) == Some(c)
+    }
+}
+
+type Count = i32;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Point(i32, i32);
+
+#[derive(Debug, Copy, Clone)]
+struct Offset(i32, i32);
+
+pub struct Source {
+    rows: Vec<Vec<char>>,
+    width: usize,
+    height: usize,
+}
+
+impl std::ops::Neg for Offset {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1)
+    }
+}
+
+impl std::ops::Add<Offset> for Point {
+    type Output = Self;
+    fn add(self, other: Offset) -> Self::Output {
+        Self(self.0 + other.0, self.1 + other.1)
+    }
+}
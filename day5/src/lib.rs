@@ -1,12 +1,10 @@
-// public modules for bencher
-pub mod files;
-pub mod input_finder;
-pub mod misc;
-
 use std::cmp::Ordering;
 use std::ops::Index;
 
-use misc::AResult;
+use aoc_core::error::{parse_at, ParseError};
+use aoc_core::files::{split_lines, split_sections};
+use aoc_core::misc::AResult;
+use aoc_core::solution::Solution;
 
 #[derive(Debug)]
 struct OrderingRule {
@@ -21,46 +19,81 @@ struct MatchedOrderingRule<'a> {
 }
 type Update = Vec<i32>;
 
-pub fn run(filename: &str, part: u8) -> AResult<i32> {
-    let input = files::load_full_input_as_string(filename)?;
-    run_on_string(&input, part)
+pub struct Solver;
+
+pub struct SafetyManual {
+    rules: Vec<OrderingRule>,
+    updates: Vec<Update>,
 }
 
-pub fn run_on_string(input: &str, part: u8) -> AResult<i32> {
-    let sections = input.split("\n\n").collect::<Vec<_>>();
+impl Solution for Solver {
+    type Input = SafetyManual;
+    type Answer = i32;
+
+    fn parse(input: &str) -> AResult<SafetyManual> {
+        parse_manual(input)
+    }
+
+    fn part1(manual: &SafetyManual) -> AResult<i32> {
+        Ok(sum_middle_pages(manual, 1))
+    }
+
+    fn part2(manual: &SafetyManual) -> AResult<i32> {
+        Ok(sum_middle_pages(manual, 2))
+    }
+}
+
+fn parse_manual(input: &str) -> AResult<SafetyManual> {
+    let sections = split_sections(input).collect::<Vec<_>>();
+
+    if sections.len() < 2 {
+        return Err(ParseError::at_end(input, "expected a blank line before the updates").into());
+    }
 
-    let rules = sections[0]
-        .lines()
+    let rules = split_lines(sections[0])
         .map(|line| {
             let pair = line
                 .split('|')
-                .map(|v| v.parse::<i32>())
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(|e| e.to_string())?;
+                .map(|v| parse_at::<i32>(input, v))
+                .collect::<AResult<Vec<_>>>()?;
 
             match pair.len() {
                 2 => Ok(OrderingRule {
                     lesser: pair[0],
                     greater: pair[1],
                 }),
-                e => Err(format!("Expected two elements per line but got {e}")),
+                e => Err(ParseError::at(
+                    input,
+                    line,
+                    format!("Expected two elements per line but got {e}"),
+                )
+                .into()),
             }
         })
-        .collect::<Result<Vec<OrderingRule>, _>>()?;
+        .collect::<AResult<Vec<OrderingRule>>>()?;
 
-    let updates = sections[1]
-        .lines()
+    let updates = split_lines(sections[1])
         .map(|l| {
-            l.split(',')
-                .map(|p| -> Result<i32, String> {
-                    Ok(p.parse::<i32>().map_err(|e| e.to_string())?)
-                })
-                .collect::<Result<Update, _>>()
+            let update = l
+                .split(',')
+                .map(|p| parse_at::<i32>(input, p))
+                .collect::<AResult<Update>>()?;
+
+            if update.len() % 2 == 0 {
+                return Err(ParseError::at(input, l, "what even is the middle?").into());
+            }
+
+            Ok(update)
         })
-        .collect::<Result<Vec<Update>, _>>()?;
+        .collect::<AResult<Vec<Update>>>()?;
 
+    Ok(SafetyManual { rules, updates })
+}
+
+fn sum_middle_pages(manual: &SafetyManual, part: u8) -> i32 {
     let matching_rules = |update: &Update| {
-        rules
+        manual
+            .rules
             .iter()
             .map(|r| {
                 (
@@ -83,7 +116,8 @@ pub fn run_on_string(input: &str, part: u8) -> AResult<i32> {
         matched_rules: Vec<MatchedOrderingRule<'a>>,
     }
 
-    let relevant_updates: (Vec<UpdateAndMatchingRules>, Vec<UpdateAndMatchingRules>) = updates
+    let relevant_updates: (Vec<UpdateAndMatchingRules>, Vec<UpdateAndMatchingRules>) = manual
+        .updates
         .iter()
         .map(|update| UpdateAndMatchingRules {
             update,
@@ -97,7 +131,7 @@ pub fn run_on_string(input: &str, part: u8) -> AResult<i32> {
             .iter()
             .map(|u| (*u.update).clone()) // only because part 2 needs this of us
             .collect::<Vec<Update>>(),
-        2 => relevant_updates
+        _ => relevant_updates
             .1 // rejected updates
             .iter()
             .map(|update| {
@@ -116,17 +150,10 @@ pub fn run_on_string(input: &str, part: u8) -> AResult<i32> {
                 updated
             })
             .collect::<Vec<Update>>(),
-        _ => panic!("uknown part"),
     };
 
-    Ok(valid_updates
+    valid_updates
         .iter()
-        .map(|u: &Update| {
-            let update = u;
-            if update.len() % 2 == 0 {
-                panic!("what even is the middle?")
-            }
-            update.index(update.len() / 2)
-        })
-        .sum())
+        .map(|update: &Update| update.index(update.len() / 2))
+        .sum()
 }
@@ -1 +0,0 @@
-pub type AResult<T> = Result<T, String>;
@@ -1,39 +1,40 @@
-// public modules for bencher
-pub mod files;
-pub mod input_finder;
-pub mod misc;
+use aoc_core::error::ParseError;
+use aoc_core::files::split_lines;
+use aoc_core::misc::AResult;
+use aoc_core::solution::Solution;
+use aoc_core::visual::{self, Cell, Frame, Style};
 
-use std::fmt::Write;
+pub struct Solver;
 
-use misc::AResult;
+impl Solution for Solver {
+    type Input = World;
+    type Answer = i32;
 
-pub fn run(filename: &str, part: u8) -> AResult<i32> {
-    let input = files::load_full_input_as_string(filename)?;
-    run_on_string(&input, part)
-}
+    fn parse(input: &str) -> AResult<World> {
+        World::from_string(input)
+    }
+
+    fn part1(world: &World) -> AResult<i32> {
+        patrol(world, 1)
+    }
 
-pub fn run_on_string(input: &str, part: u8) -> AResult<i32> {
-    // Note: Part 3 is part 1 with visualisation, part 4 is part 2 with visualisation
-    let visualise: bool = part == 3;
+    fn part2(world: &World) -> AResult<i32> {
+        patrol(world, 2)
+    }
+}
 
-    let mut world = World::from_string(input)?;
+fn patrol(world: &World, part: u8) -> AResult<i32> {
+    let mut world = world.clone();
 
-    print_board(visualise, &world, 0, 1000)?;
+    visual::frame(|| world.frame(0))?;
     while world.guard.state == GuardState::StillWalking {
         world.guard.step(&mut world.map, false);
-        print_board(visualise, &world, 0, 100)?;
-    }
-
-    if visualise {
-        print!("{world}");
-        println!("loops found: 0");
+        visual::frame(|| world.frame(0))?;
     }
 
     let mut result = world.guard.visited_count;
 
-    if part == 2 || part == 4 {
-        let visualise: bool = part == 4;
-
+    if part == 2 {
         let risk_map = world.map.clone();
         let mut loopable_positions = 0;
 
@@ -46,17 +47,18 @@ pub fn run_on_string(input: &str, part: u8) -> AResult<i32> {
 
                 world.reset();
                 world.map.place_obstruction_at(p);
-                print_board(visualise, &world, loopable_positions, 500)?;
+                visual::frame(|| world.frame(loopable_positions))?;
 
                 while world.guard.state == GuardState::StillWalking {
                     world.guard.step(&mut world.map, true);
 
-                    print_board(visualise, &world, loopable_positions, 50)?;
+                    visual::frame(|| world.frame(loopable_positions))?;
 
                     match world.guard.state {
                         GuardState::StillWalking => {}
                         GuardState::StuckInLoop => {
                             loopable_positions += 1;
+                            visual::event(|| format!("loop with an obstruction at {row},{col}"))?;
                             break;
                         }
                         GuardState::LeftTheMap => break,
@@ -65,26 +67,13 @@ pub fn run_on_string(input: &str, part: u8) -> AResult<i32> {
             }
         }
 
-        print_board(visualise, &world, loopable_positions, 0)?;
-
         result = loopable_positions;
     }
     Ok(result)
 }
 
-fn print_board(visualise: bool, world: &World, loopable_positions: i32, delay: u64) -> AResult<()> {
-    if !visualise {
-        return Ok(());
-    }
-
-    print!("\x1b[s");
-    print!("{world}");
-    println!("loops found: {loopable_positions}");
-    print!("\x1b[u");
-    wait(delay)
-}
-
-struct World {
+#[derive(Clone)]
+pub struct World {
     map: TimeMap,
     initial_map: TimeMap,
     guard: Guard,
@@ -94,36 +83,45 @@ struct World {
 impl World {
     fn from_string(input: &str) -> AResult<Self> {
         let mut guard: Option<Guard> = None;
+        let mut width: Option<usize> = None;
 
-        let data = input
-            .lines()
+        let data = split_lines(input)
             .enumerate()
-            .map(|line| {
-                line.1
-                    .chars()
-                    .enumerate()
-                    .map(|c| match c.1 {
-                        '#' => Ok(Square::with_access(Accessibility::Obstructed(
-                            ObstructionType::Original,
-                        ))),
-                        '0' => Ok(Square::with_access(Accessibility::Obstructed(
-                            ObstructionType::Introduced,
-                        ))),
-                        '>' => init_guard(&mut guard, line.0, c.0, Direction::Right),
-                        '<' => init_guard(&mut guard, line.0, c.0, Direction::Left),
-                        '^' => init_guard(&mut guard, line.0, c.0, Direction::Up),
-                        'v' => init_guard(&mut guard, line.0, c.0, Direction::Down),
-                        '.' => Ok(Square::with_access(Accessibility::Free)),
-                        invalid => {
-                            Err(format!("failed to understand input - found {invalid}").to_string())
-                        }
+            .map(|(row, line)| {
+                if *width.get_or_insert(line.len()) != line.len() {
+                    return Err(ParseError::at(
+                        input,
+                        line,
+                        "every row of the map must be the same width",
+                    ));
+                }
+
+                line.char_indices()
+                    .map(|(col, c)| {
+                        let square = match c {
+                            '#' => Ok(Square::with_access(Accessibility::Obstructed(
+                                ObstructionType::Original,
+                            ))),
+                            '0' => Ok(Square::with_access(Accessibility::Obstructed(
+                                ObstructionType::Introduced,
+                            ))),
+                            '>' => init_guard(&mut guard, row, col, Direction::Right),
+                            '<' => init_guard(&mut guard, row, col, Direction::Left),
+                            '^' => init_guard(&mut guard, row, col, Direction::Up),
+                            'v' => init_guard(&mut guard, row, col, Direction::Down),
+                            '.' => Ok(Square::with_access(Accessibility::Free)),
+                            invalid => Err(format!("failed to understand input - found {invalid}")),
+                        };
+                        square.map_err(|reason| {
+                            ParseError::at(input, &line[col..col + c.len_utf8()], reason)
+                        })
                     })
                     .collect()
             })
-            .collect::<Result<Vec<Vec<Square>>, String>>()?;
+            .collect::<Result<Vec<Vec<Square>>, ParseError>>()?;
 
         let map = TimeMap::from(data);
-        let guard = guard.ok_or("missing guard".to_string())?;
+        let guard = guard.ok_or_else(|| ParseError::at_end(input, "missing guard"))?;
 
         Ok(Self {
             map: map.clone(),
@@ -137,6 +135,13 @@ impl World {
         self.guard = self.initial_guard.clone();
         self.map = self.initial_map.clone();
     }
+
+    fn frame(&self, loopable_positions: i32) -> Frame {
+        self.map.frame(&self.guard).with_caption(format!(
+            "visited: {}, loops found: {loopable_positions}",
+            self.guard.visited_count
+        ))
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -166,7 +171,7 @@ struct Position {
 
 impl Position {
     fn new(row: i32, col: i32) -> Self {
-        Self { row: row, col: col }
+        Self { row, col }
     }
     fn from(row: usize, col: usize) -> Self {
         Self::new(row as i32, col as i32)
@@ -202,7 +207,7 @@ fn init_guard(
     row: usize,
     col: usize,
     direction: Direction,
-) -> AResult<Square> {
+) -> Result<Square, String> {
     match guard {
         Some(_) => Err("found two guards?".to_string()),
         None => {
@@ -299,7 +304,7 @@ impl Visited {
             Direction::Down => self.down = true,
         };
 
-        return true;
+        true
     }
 
     fn new(direction: Direction) -> Self {
@@ -352,7 +357,7 @@ impl TimeMap {
     fn from(data: Vec<Vec<Square>>) -> Self {
         Self {
             height: data.len() as i32,
-            width: if data.len() > 0 { data[0].len() } else { 0 } as i32,
+            width: if !data.is_empty() { data[0].len() } else { 0 } as i32,
             data,
         }
     }
@@ -374,61 +379,70 @@ impl TimeMap {
     fn sensible_place_for_obstruction(&self, p: Position) -> bool {
         // no point placing an obstruction where the guard never passes
         let square = self.read(p);
-        return match square.accessibility {
+        match square.accessibility {
             Accessibility::Obstructed(_) => false,
             Accessibility::Free if !square.visited.is_visited() => false,
             _ => true,
-        };
+        }
     }
 
     fn place_obstruction_at(&mut self, p: Position) {
         self.at(p).unwrap().accessibility = Accessibility::Obstructed(ObstructionType::Introduced)
     }
 
-    fn display(&self, f: &mut std::fmt::Formatter<'_>, guard: &Guard) -> std::fmt::Result {
-        f.write_char('╔')?;
-        for _ in 0..self.width {
-            f.write_char('═')?
-        }
-        f.write_char('╗')?;
-        f.write_char('\n')?;
-
-        self.data.iter().enumerate().try_for_each(|row| {
-            f.write_char('║')?;
-            row.1.iter().enumerate().try_for_each(|point| {
-                let c = match point.1.accessibility {
-                    Accessibility::Free if guard.is_at(row.0, point.0) => guard.as_char(),
-                    Accessibility::Free if point.1.visited.is_visited() => 'X',
-                    Accessibility::Free => '·',
-                    Accessibility::Obstructed(ObstructionType::Original) => '█',
-                    Accessibility::Obstructed(ObstructionType::Introduced) => 'Ø',
-                };
-                f.write_char(c)
-            })?;
-            f.write_char('║')?;
-            f.write_char('\n')
-        })?;
-
-        f.write_char('╚')?;
-        for _ in 0..self.width {
-            f.write_char('═')?
+    fn frame(&self, guard: &Guard) -> Frame {
+        let edge = |left, right| {
+            std::iter::once(Cell::plain(left))
+                .chain((0..self.width).map(|_| Cell::plain('═')))
+                .chain(std::iter::once(Cell::plain(right)))
+        };
+
+        let mut frame = Frame::new();
+        frame.push_row(edge('╔', '╗'));
+        for (row, squares) in self.data.iter().enumerate() {
+            let cells =
+                squares
+                    .iter()
+                    .enumerate()
+                    .map(|(col, square)| match square.accessibility {
+                        Accessibility::Free if guard.is_at(row, col) => {
+                            Cell::new(guard.as_char(), Style::Marker)
+                        }
+                        Accessibility::Free if square.visited.is_visited() => {
+                            Cell::new('X', Style::Path)
+                        }
+                        Accessibility::Free => Cell::new('·', Style::Faint),
+                        Accessibility::Obstructed(ObstructionType::Original) => {
+                            Cell::new('█', Style::Wall)
+                        }
+                        Accessibility::Obstructed(ObstructionType::Introduced) => {
+                            Cell::new('Ø', Style::Highlight)
+                        }
+                    });
+            frame.push_row(
+                std::iter::once(Cell::plain('║'))
+                    .chain(cells)
+                    .chain(std::iter::once(Cell::plain('║'))),
+            );
         }
-        f.write_char('╝')?;
-        f.write_char('\n')?;
-        f.write_str(format!("Visited: {}", guard.visited_count).as_str())?;
-        f.write_char('\n')?;
-        Ok(())
+        frame.push_row(edge('╚', '╝'));
+        frame
     }
 }
 
-impl std::fmt::Display for World {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.map.display(f, &self.guard)
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let error = Solver::parse("....#\n..^\n.....\n").err().unwrap();
+        assert!(
+            error
+                .to_string()
+                .starts_with("<input:2:1> :: every row of the map must be the same width"),
+            "{error}"
+        );
+        assert!(Solver::parse("....#\n..^..\n.....\n").is_ok());
     }
 }
-
-fn wait(milis: u64) -> AResult<()> {
-    std::io::Write::flush(&mut std::io::stdout()).map_err(|e| e.to_string())?;
-    std::thread::sleep(std::time::Duration::from_millis(milis));
-    Ok(())
-}
@@ -1,18 +1,29 @@
-// public modules for bencher
-pub mod files;
-pub mod input_finder;
-pub mod misc;
+use aoc_core::error::{parse_at, Error};
+use aoc_core::files::{self, split_lines};
+use aoc_core::misc::AResult;
+use aoc_core::solution::Solution;
 
-use misc::AResult;
+pub struct Solver;
 
-pub fn run(filename: &str, part: u8) -> AResult<i64> {
-    let input = files::load_full_input_as_string(filename)?;
-    run_on_string(&input, part)
+impl Solution for Solver {
+    type Input = Vec<Equation>;
+    type Answer = i64;
+
+    fn parse(input: &str) -> AResult<Vec<Equation>> {
+        parse_equations(input)
+    }
+
+    fn part1(equations: &Vec<Equation>) -> AResult<i64> {
+        Ok(calibrate(equations, 1))
+    }
+
+    fn part2(equations: &Vec<Equation>) -> AResult<i64> {
+        Ok(calibrate(equations, 2))
+    }
 }
 
 #[derive(Debug, Default, Clone)]
-struct Input {
-    line_number: usize,
+pub struct Equation {
     result: i64,
     args: Vec<Token>,
     operators: Vec<usize>,
@@ -27,49 +38,78 @@ enum Token {
     Value(i64, i64),
 }
 
-fn parse_int(value: &str) -> AResult<i64> {
-    value.parse::<i64>().map_err(|e| e.to_string())
+fn parse_equations(input: &str) -> AResult<Vec<Equation>> {
+    split_lines(input)
+        .map(|line| parse_equation(input, line))
+        .collect()
 }
 
-pub fn run_on_string(input: &str, part: u8) -> AResult<i64> {
-    input
-        .lines()
-        .enumerate()
-        .map(|line| {
-            let mut calc = Input::default();
-            calc.line_number = line.0;
-            let mut parts = line.1.split(": ");
-
-            if let Some(result) = parts.next() {
-                calc.result = parse_int(result)?;
+/// `line` is a slice of `input`, which errors are located in
+fn parse_equation(input: &str, line: &str) -> AResult<Equation> {
+    let mut calc = Equation::default();
+    let mut parts = line.split(": ");
+
+    if let Some(result) = parts.next() {
+        calc.result = parse_at::<i64>(input, result)?;
+    }
+
+    if let Some(args) = parts.next() {
+        args.split(' ').try_for_each(|arg| -> AResult<()> {
+            if !calc.args.is_empty() {
+                let position = calc.args.len();
+                calc.operators.push(position);
+                calc.args.push(Token::Unknown);
             }
+            calc.args.push(Token::Value(
+                parse_at::<i64>(input, arg)?,
+                10_i64.pow(arg.len() as u32),
+            ));
+            Ok(())
+        })?;
+    }
 
-            if let Some(args) = parts.next() {
-                args.split(' ').try_for_each(|arg| -> AResult<()> {
-                    if !calc.args.is_empty() {
-                        let position = calc.args.len();
-                        calc.operators.push(position);
-                        calc.args.push(Token::Unknown);
-                    }
-                    calc.args.push(Token::Value(
-                        parse_int(arg)?,
-                        (10 as i64).pow(arg.len() as u32),
-                    ));
-                    Ok(())
-                })?;
+    Ok(calc)
+}
+
+/// As the solver for both parts, reading the input a line at a time in the one pass,
+/// so it needn't fit in memory and stdin only needs reading once
+pub fn calibrate_input(filename: &str) -> AResult<(i64, i64)> {
+    calibrate_lines(filename, files::input_lines(filename)?)
+}
+
+fn calibrate_lines(
+    filename: &str,
+    lines: impl Iterator<Item = AResult<String>>,
+) -> AResult<(i64, i64)> {
+    let mut totals = (0, 0);
+    for (index, line) in lines.enumerate() {
+        let line = line?;
+        let calc = parse_equation(&line, &line).map_err(|e| match e {
+            Error::Parse(mut e) => {
+                e.line = index + 1;
+                Error::Parse(e).in_file(filename)
             }
+            e => e,
+        })?;
+        if could_be_true(calc.clone(), 1) {
+            totals.0 += calc.result;
+        }
+        if could_be_true(calc.clone(), 2) {
+            totals.1 += calc.result;
+        }
+    }
+    Ok(totals)
+}
 
-            Ok(calc)
-        })
-        .filter_map(|calc| match calc {
-            Ok(calc) if could_be_true(calc.clone(), part) => Some(Ok(calc.result as i64)),
-            Ok(_) => None,
-            Err(e) => Some(Err(e)),
-        })
-        .sum::<Result<i64, String>>()
+fn calibrate(equations: &[Equation], part: u8) -> i64 {
+    equations
+        .iter()
+        .filter(|calc| could_be_true((*calc).clone(), part))
+        .map(|calc| calc.result)
+        .sum()
 }
 
-impl std::fmt::Display for Input {
+impl std::fmt::Display for Equation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for arg in &self.args {
             match arg {
@@ -119,7 +159,7 @@ impl Permutation {
     }
 }
 
-fn could_be_true(mut calc: Input, part: u8) -> bool {
+fn could_be_true(mut calc: Equation, part: u8) -> bool {
     let operator_count = calc.operators.len();
     let mut p = Permutation::new(operator_count, part);
     loop {
@@ -172,3 +212,48 @@ fn could_be_true(mut calc: Input, part: u8) -> bool {
 
     false
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+    fn lines(text: &str) -> files::Lines {
+        files::Lines::new(
+            "example",
+            Box::new(std::io::Cursor::new(text.as_bytes().to_vec())),
+        )
+    }
+
+    #[test]
+    fn reads_lines_to_the_same_answers_as_the_solver() {
+        assert_eq!(
+            (
+                Solver::solve(EXAMPLE, 1).unwrap(),
+                Solver::solve(EXAMPLE, 2).unwrap()
+            ),
+            calibrate_lines("example", lines(EXAMPLE)).unwrap()
+        );
+        assert_eq!(
+            (3749, 11387),
+            calibrate_lines("example", lines(EXAMPLE)).unwrap()
+        );
+    }
+
+    #[test]
+    fn places_errors_on_their_line() {
+        let error = calibrate_lines("example", lines("190: 10 19\n83: 17 x\n")).unwrap_err();
+        assert!(error.to_string().starts_with("<example:2:"), "{error}");
+    }
+}
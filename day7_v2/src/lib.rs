@@ -1,18 +1,30 @@
-// public modules for bencher
-pub mod files;
-pub mod input_finder;
-pub mod misc;
-use misc::AResult;
+use aoc_core::error::parse_at;
+use aoc_core::files::split_lines;
+use aoc_core::misc::AResult;
+use aoc_core::solution::Solution;
 use rayon::prelude::*;
 
-pub fn run(filename: &str, part: u8) -> AResult<i64> {
-    let input = files::load_full_input_as_string(filename)?;
-    run_on_string(&input, part)
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Equation>;
+    type Answer = i64;
+
+    fn parse(input: &str) -> AResult<Vec<Equation>> {
+        parse_equations(input)
+    }
+
+    fn part1(equations: &Vec<Equation>) -> AResult<i64> {
+        Ok(calibrate(equations, 1))
+    }
+
+    fn part2(equations: &Vec<Equation>) -> AResult<i64> {
+        Ok(calibrate(equations, 2))
+    }
 }
 
 #[derive(Debug, Default, Clone)]
-struct Input {
-    line_number: usize,
+pub struct Equation {
     result: i64,
     args: Vec<Token>,
 }
@@ -23,66 +35,57 @@ struct Token {
     offset: i64,
 }
 
-pub fn run_on_string(input: &str, part: u8) -> AResult<i64> {
-    const GROUP_COUNT: usize = 4;
-    let mut groups: Vec<Vec<Input>> = vec![Vec::new(); GROUP_COUNT];
-
-    input
-        .lines()
-        .enumerate()
-        .map(|(line_number, line)| -> AResult<(usize, Input)> {
-            let mut calc = Input::default();
-            calc.line_number = line_number;
+fn parse_equations(input: &str) -> AResult<Vec<Equation>> {
+    split_lines(input)
+        .map(|line| -> AResult<Equation> {
+            let mut calc = Equation::default();
             let mut parts = line.split(": ");
 
             if let Some(result) = parts.next() {
-                calc.result = parse_int(result)?;
+                calc.result = parse_at::<i64>(input, result)?;
             }
 
             if let Some(args) = parts.next() {
                 args.split(' ').try_for_each(|arg| -> AResult<()> {
                     calc.args.push(Token {
-                        value: parse_int(arg)?,
-                        offset: (10 as i64).pow(arg.len() as u32),
+                        value: parse_at::<i64>(input, arg)?,
+                        offset: 10_i64.pow(arg.len() as u32),
                     });
                     Ok(())
                 })?;
             }
 
-            Ok((line_number, calc))
+            Ok(calc)
         })
-        .try_for_each(|f| {
-            if let Ok((line_number, input)) = f {
-                groups[line_number % GROUP_COUNT].push(input);
-                Ok(())
-            } else {
-                Err(f.err().unwrap())
-            }
-        })?;
+        .collect()
+}
+
+fn calibrate(equations: &[Equation], part: u8) -> i64 {
+    const GROUP_COUNT: usize = 4;
+    let mut groups: Vec<Vec<&Equation>> = vec![Vec::new(); GROUP_COUNT];
+
+    for (line_number, calc) in equations.iter().enumerate() {
+        groups[line_number % GROUP_COUNT].push(calc);
+    }
 
     groups
         .into_par_iter()
         .map(|calc_set| {
             calc_set
                 .iter()
-                .map(|calc: &Input| -> AResult<i64> {
-                    let result = calc.result;
-                    Ok(if could_be_true(calc, 0, 0, part) {
-                        result
+                .map(|calc| {
+                    if could_be_true(calc, 0, 0, part) {
+                        calc.result
                     } else {
                         0
-                    })
+                    }
                 })
-                .sum::<Result<i64, String>>()
+                .sum::<i64>()
         })
-        .sum::<Result<i64, String>>()
-}
-
-fn parse_int(value: &str) -> AResult<i64> {
-    value.parse::<i64>().map_err(|e| e.to_string())
+        .sum()
 }
 
-fn could_be_true(calc: &Input, partial_result: i64, index: usize, part: u8) -> bool {
+fn could_be_true(calc: &Equation, partial_result: i64, index: usize, part: u8) -> bool {
     if partial_result > calc.result {
         // answers only ever grow, so we can short circuit here
         return false;
@@ -94,7 +97,7 @@ fn could_be_true(calc: &Input, partial_result: i64, index: usize, part: u8) -> b
 
     let token = calc.args[index];
 
-    return could_be_true(calc, partial_result + token.value, index + 1, part)
+    could_be_true(calc, partial_result + token.value, index + 1, part)
         || could_be_true(calc, partial_result * token.value, index + 1, part)
         || (part == 2
             && could_be_true(
@@ -102,5 +105,5 @@ fn could_be_true(calc: &Input, partial_result: i64, index: usize, part: u8) -> b
                 partial_result * token.offset + token.value,
                 index + 1,
                 part,
-            ));
+            ))
 }
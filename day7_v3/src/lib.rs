@@ -1,18 +1,30 @@
-// public modules for bencher
-pub mod files;
-pub mod input_finder;
-pub mod misc;
-use misc::AResult;
-
-pub fn run(filename: &str, part: u8) -> AResult<i64> {
-    let input = files::load_full_input_as_string(filename)?;
-    run_on_string(&input, part)
+use aoc_core::error::parse_at;
+use aoc_core::files::split_lines;
+use aoc_core::misc::AResult;
+use aoc_core::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Equation>;
+    type Answer = i64;
+
+    fn parse(input: &str) -> AResult<Vec<Equation>> {
+        parse_equations(input)
+    }
+
+    fn part1(equations: &Vec<Equation>) -> AResult<i64> {
+        Ok(calibrate(equations, 1))
+    }
+
+    fn part2(equations: &Vec<Equation>) -> AResult<i64> {
+        Ok(calibrate(equations, 2))
+    }
 }
 
 #[derive(Debug, Default, Clone)]
-struct Input {
+pub struct Equation {
     result: i64,
-    input: String,
     args: Vec<Token>,
 }
 
@@ -22,40 +34,40 @@ struct Token {
     offset: i64,
 }
 
-pub fn run_on_string(input: &str, part: u8) -> AResult<i64> {
-    input
-        .lines()
-        .map(|line| -> AResult<i64> {
-            let mut calc = Input::default();
+fn parse_equations(input: &str) -> AResult<Vec<Equation>> {
+    split_lines(input)
+        .map(|line| -> AResult<Equation> {
+            let mut calc = Equation::default();
             let mut parts = line.split(": ");
 
             if let Some(result) = parts.next() {
-                calc.result = parse_int(result)?;
+                calc.result = parse_at::<i64>(input, result)?;
             }
 
             if let Some(args) = parts.next() {
-                calc.input = args.to_string();
                 args.split(' ').try_for_each(|arg| -> AResult<()> {
                     calc.args.push(Token {
-                        value: parse_int(arg)?,
-                        offset: (10 as i64).pow(arg.len() as u32),
+                        value: parse_at::<i64>(input, arg)?,
+                        offset: 10_i64.pow(arg.len() as u32),
                     });
                     Ok(())
                 })?;
             }
 
-            let result = could_be_true(&calc, calc.result, calc.args.len() as i32 - 1, part);
-
-            Ok(if result { calc.result } else { 0 })
+            Ok(calc)
         })
-        .sum::<Result<i64, String>>()
+        .collect()
 }
 
-fn parse_int(value: &str) -> AResult<i64> {
-    value.parse::<i64>().map_err(|e| e.to_string())
+fn calibrate(equations: &[Equation], part: u8) -> i64 {
+    equations
+        .iter()
+        .filter(|calc| could_be_true(calc, calc.result, calc.args.len() as i32 - 1, part))
+        .map(|calc| calc.result)
+        .sum()
 }
 
-fn could_be_true(calc: &Input, partial_result: i64, index: i32, part: u8) -> bool {
+fn could_be_true(calc: &Equation, partial_result: i64, index: i32, part: u8) -> bool {
     if index < 0 {
         // ran out of possible values to operate on
         // if we are now at exactly zero then this branch passed
@@ -1,15 +1,29 @@
-// public modules for bencher
-pub mod files;
-pub mod input_finder;
-pub mod misc;
-
 use std::ops::Add;
+use std::path::Path;
+
+use aoc_core::files::split_lines;
+use aoc_core::misc::AResult;
+use aoc_core::solution::Solution;
+use aoc_core::svg::{Layer, Mark, Svg};
+use aoc_core::visual::{self, Cell, Frame, Style};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Grid;
+    type Answer = i64;
+
+    fn parse(input: &str) -> AResult<Grid> {
+        Ok(Grid::new(input))
+    }
 
-use misc::AResult;
+    fn part1(grid: &Grid) -> AResult<i64> {
+        count_antinodes(grid, 1)
+    }
 
-pub fn run(filename: &str, part: u8) -> AResult<i64> {
-    let input = files::load_full_input_as_string(filename)?;
-    run_on_string(&input, part)
+    fn part2(grid: &Grid) -> AResult<i64> {
+        count_antinodes(grid, 2)
+    }
 }
 
 #[derive(Debug, PartialEq, Hash, Eq, Clone, Copy)]
@@ -38,7 +52,7 @@ impl Add for Point {
 }
 
 #[derive(Debug)]
-struct Grid {
+pub struct Grid {
     antennas: std::collections::HashMap<char, Vec<Point>>,
     width: i32,
     height: i32,
@@ -52,8 +66,7 @@ impl Grid {
             height: 0,
         };
 
-        partial.height = input
-            .lines()
+        partial.height = split_lines(input)
             .enumerate()
             .map(|(row, line)| {
                 partial.width = line
@@ -64,7 +77,7 @@ impl Grid {
                             partial
                                 .antennas
                                 .entry(c)
-                                .or_insert_with(|| Vec::new())
+                                .or_default()
                                 .push(Point::from(row, col));
                         }
                     })
@@ -74,14 +87,36 @@ impl Grid {
 
         partial
     }
+
+    fn antenna_at(&self, p: Point) -> Option<char> {
+        self.antennas
+            .iter()
+            .find(|(_, points)| points.contains(&p))
+            .map(|(&frequency, _)| frequency)
+    }
 }
 
-pub fn run_on_string(input: &str, part: u8) -> AResult<i64> {
-    let grid = Grid::new(input);
+fn count_antinodes(grid: &Grid, part: u8) -> AResult<i64> {
+    Ok(find_antinodes(grid, part)?.len() as i64)
+}
+
+/// Saves the antenna map as an SVG picture, with the antinodes the part finds marked on it
+pub fn export_svg(grid: &Grid, part: u8, path: impl AsRef<Path>) -> AResult<()> {
+    let antinodes = find_antinodes(grid, part)?;
+    Svg::from_grid(grid.height as usize, grid.width as usize, |(row, col)| {
+        let p = Point::from(row, col);
+        let antinode = antinodes.contains(&p).then_some(Mark::On(Layer::Antinodes));
+        antinode
+            .into_iter()
+            .chain(grid.antenna_at(p).map(Mark::Label))
+    })
+    .save(path)
+}
 
+fn find_antinodes(grid: &Grid, part: u8) -> AResult<std::collections::HashSet<Point>> {
     let mut antinodes: std::collections::HashSet<Point> = std::collections::HashSet::new();
 
-    for (_, antennas) in &grid.antennas {
+    for antennas in grid.antennas.values() {
         for (first, second) in generate_pairs(antennas, part) {
             let diff = Point::new(first.row - second.row, first.col - second.col);
             let mut antinode = *first;
@@ -103,10 +138,10 @@ pub fn run_on_string(input: &str, part: u8) -> AResult<i64> {
                 }
             }
         }
+        visual::frame(|| frame(grid, &antinodes))?;
     }
-    print(&grid, &antinodes);
 
-    Ok(antinodes.len() as i64)
+    Ok(antinodes)
 }
 
 fn generate_pairs(items: &Vec<Point>, part: u8) -> Vec<(&Point, &Point)> {
@@ -121,24 +156,56 @@ fn generate_pairs(items: &Vec<Point>, part: u8) -> Vec<(&Point, &Point)> {
     result
 }
 
-fn print(grid: &Grid, antinodes: &std::collections::HashSet<Point>) {
+fn frame(grid: &Grid, antinodes: &std::collections::HashSet<Point>) -> Frame {
+    let mut frame = Frame::new();
     for row in 0..grid.height {
-        for col in 0..grid.width {
+        frame.push_row((0..grid.width).map(|col| {
             let p = Point::new(row, col);
-            let antenna = grid.antennas.iter().find(|(_, points)| points.contains(&p));
-            let antinode = antinodes.iter().find(|point| **point == p);
-            if antinode.is_some() {
-                print!("\x1b[7m");
-            }
-            if let Some(antenna) = antenna {
-                print!("{}", antenna.0);
-            } else {
-                print!(".");
-            }
-            if antinode.is_some() {
-                print!("\x1b[27m");
-            }
-        }
-        print!("\n");
+            let style = match antinodes.contains(&p) {
+                true => Style::Highlight,
+                false => Style::Plain,
+            };
+            Cell::new(grid.antenna_at(p).unwrap_or('.'), style)
+        }));
+    }
+    frame.with_caption(format!("antinodes: {}", antinodes.len()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn exports_the_antennas_and_antinodes() {
+        let grid = Grid::new(
+            "\
+..........
+..........
+..........
+....a.....
+..........
+.....a....
+..........
+..........
+..........
+..........",
+        );
+        let path = std::env::temp_dir().join(format!("day8-{}.svg", std::process::id()));
+        export_svg(&grid, 1, &path).unwrap();
+        let svg = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert!(svg.contains(
+            "<g id=\"antinodes\" fill=\"none\" stroke=\"#ff5a36\" stroke-width=\"1.5\">\n\
+             <circle cx=\"35\" cy=\"15\" r=\"3.5\"/>\n\
+             <circle cx=\"65\" cy=\"75\" r=\"3.5\"/>\n\
+             </g>\n"
+        ));
+        assert!(svg.contains(
+            "<text x=\"45\" y=\"35\">a</text>\n\
+             <text x=\"55\" y=\"55\">a</text>\n\
+             </g>\n"
+        ));
+        assert!(svg.contains("<g id=\"labels\""), "{svg}");
     }
 }